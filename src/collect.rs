@@ -1,15 +1,15 @@
 use crate::config::{Config, Table};
 use crate::db::Db;
-use crate::transform::apply_transformations;
+use crate::transform::{sample_distributions, RowTransformer};
 use s3::bucket::Bucket;
 use s3::creds::Credentials;
+use std::error::Error;
 use std::time::Instant;
 use tempfile::NamedTempFile;
-use tokio_postgres::Error;
 
-pub async fn collect(config: &Config) -> Result<(), Error> {
+pub async fn collect(config: &Config) -> Result<(), Box<dyn Error>> {
     let source = &config.source;
-    let db = Db::new(&source.connection_uri).await;
+    let db = Db::new(source.connection_uri.expose()).await;
     log::debug!("Connecting to source database");
 
     for table_def in &source.tables {
@@ -20,20 +20,18 @@ pub async fn collect(config: &Config) -> Result<(), Error> {
         if let Some(count) = &table_def.generate {
             log::debug!("Generating new table {} with {} rows", table, count);
 
-            generate_csv(&config, &table_def, count).await;
+            generate_csv(config, table_def, count).await?;
         } else {
             let count = db.count(table).await.unwrap();
             log::debug!("Processing table {} with {} rows", table, count);
 
             if let Some(from) = &table_def.from {
-                let db = Db::new(&config.destination.connection_uri).await;
+                let db = Db::new(config.destination.connection_uri.expose()).await;
                 log::debug!("Connecting to target database");
 
-                db.unload(&from, &config.store.bucket, table).await.unwrap();
+                db.unload(from, &config.store, table).await.unwrap();
             } else {
-                db.unload_table(&table, &table_def.limit, &config.store.bucket)
-                    .await
-                    .unwrap();
+                db.unload_table(table_def, &config.store).await?;
             }
         }
         let elapsed = now.elapsed();
@@ -49,12 +47,16 @@ pub async fn collect(config: &Config) -> Result<(), Error> {
     Ok(())
 }
 
-async fn generate_csv(config: &Config, table_def: &Table, count: &usize) {
+async fn generate_csv(
+    config: &Config,
+    table_def: &Table,
+    count: &usize,
+) -> Result<(), Box<dyn Error>> {
     let store = &config.store;
 
     let credentials = Credentials::new(
-        Some(&store.aws_access_key_id.clone()),
-        Some(&store.aws_secret_access_key.clone()),
+        store.aws_access_key_id.as_ref().map(|k| k.expose()),
+        store.aws_secret_access_key.as_ref().map(|k| k.expose()),
         None,
         None,
         None,
@@ -81,10 +83,16 @@ async fn generate_csv(config: &Config, table_def: &Table, count: &usize) {
 
         writer.write_record(&columns).unwrap();
 
+        let transformations =
+            sample_distributions(config.source.connection_uri.expose(), transformations).await?;
+        let mut row_transformer = RowTransformer::new(&transformations, columns.clone())
+            .map_err(|e| format!("table {}: {}", table, e))?;
         for n in 0..*count {
             let len = columns.len();
             let data: Vec<&str> = vec![""; len];
-            let row = apply_transformations(n, transformations, data, columns.clone());
+            let row = row_transformer
+                .apply(n, data)
+                .map_err(|e| format!("table {}: {}", table, e))?;
             // println!("   {:?}", row);
             writer.write_record(row).unwrap();
         }
@@ -95,4 +103,6 @@ async fn generate_csv(config: &Config, table_def: &Table, count: &usize) {
             .await
             .unwrap();
     }
+
+    Ok(())
 }
@@ -1,113 +1,698 @@
-use std::{env, fs};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fmt, fs};
 
+use regex::{Captures, Regex};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
 
 use crate::transformer::{
-    CityTransformer, DateTransformer, EmailTransformer, FirstNameTransformer, FromTransformer,
-    LastNameTransformer, NullTransformer, RandomTransformer, RandomValueTransformer,
-    RegexTransformer, ReverseTransformer, SequenceTransformer, StateTransformer, StaticTransformer,
-    StreetTransformer, Transformer, ZipCodeTransformer,
+    CityTransformer, CompanyNameTransformer, CreditCardTransformer, DateTransformer,
+    EachTransformer, EmailTransformer, FirstNameTransformer, FpeTransformer, FromTransformer,
+    FullNameTransformer, Ipv4Transformer, Ipv6Transformer, JobTitleTransformer, JsonTransformer,
+    KvTransformer, LastNameTransformer, LookupTransformer, LoremTransformer, MacAddressTransformer,
+    MaskTransformer, NoiseTransformer, NpiTransformer, NullTransformer, PhoneNumberTransformer,
+    PhoneTransformer, RandomTransformer, RandomValueTransformer, RedactTransformer,
+    RegexTransformer, ReverseTransformer, SecondaryAddressTransformer, SequenceTransformer,
+    SsnTransformer, StateTransformer, StaticTransformer, StreetTransformer, TemplateTransformer,
+    Transformer, UrlTransformer, UsernameTransformer, UuidTransformer, XmlTransformer,
+    ZipCodeTransformer,
 };
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
+    /// Default locale of the faker transformers
+    pub locale: Option<Locale>,
     pub source: Source,
     pub store: Store,
     pub destination: Destination,
     pub postprocess: Option<Vec<PostProcessTask>>,
+    /// Named transformation sets tables refer to with `transform_profile`
+    pub profiles: Option<HashMap<String, Vec<Transformation>>>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// Credential or connection string, hidden from the `Debug` output
+#[derive(PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret(value.to_string())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\"<redacted>\"")
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct PdfConfig {
     pub bucket: String,
     pub font: String,
     pub from: String,
     pub contents: String,
     pub file_name: String,
-    pub aws_access_key_id: String,
-    pub aws_secret_access_key: String,
+    pub aws_access_key_id: Secret,
+    pub aws_secret_access_key: Secret,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SqlConfig {
     pub sql: String,
-    pub connection_uri: String,
+    pub connection_uri: Secret,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct PostProcessTask {
     pub name: String,
     #[serde(flatten)]
     pub task: TaskType,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Transformation {
     pub column: String,
     #[serde(flatten)]
     pub transformer: TransformerType,
+    pub when: Option<Condition>,
+    #[serde(rename = "else")]
+    pub otherwise: Option<TransformerType>,
+    pub unique: Option<Unique>,
+}
+
+impl Transformation {
+    #[cfg(test)]
+    pub fn new(column: &str, transformer: TransformerType) -> Self {
+        Transformation {
+            column: column.to_string(),
+            transformer,
+            when: None,
+            otherwise: None,
+            unique: None,
+        }
+    }
+}
+
+/// Either `unique: true` or the uniqueness options
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(untagged)]
+pub enum Unique {
+    Enabled(bool),
+    Options(UniqueOptions),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct UniqueOptions {
+    /// Times a value is generated again before giving up, defaults to 100
+    pub max_attempts: Option<usize>,
+    /// Values tracked in memory before spilling them to disk, unlimited by default
+    pub max_in_memory: Option<usize>,
+}
+
+impl Unique {
+    pub fn options(&self) -> Option<UniqueOptions> {
+        match self {
+            Unique::Enabled(true) => Some(UniqueOptions::default()),
+            Unique::Enabled(false) => None,
+            Unique::Options(options) => Some(options.clone()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Condition {
+    pub column: String,
+    #[serde(flatten)]
+    pub predicate: Predicate,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Predicate {
+    Equals(String),
+    In(Vec<String>),
+    Matches(String),
+    IsNull(bool),
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Table {
     pub name: String,
     pub columns: Option<Vec<String>>,
     pub limit: Option<usize>,
     pub generate: Option<usize>,
     pub from: Option<String>,
+    /// Profile whose transformations apply to columns not in `transform`
+    pub transform_profile: Option<String>,
+    /// Table the data is loaded into, the same as `name` by default
+    pub destination_table: Option<String>,
+    /// Columns left out when all the columns of the table are extracted
+    pub exclude_columns: Option<Vec<String>>,
+    /// Name in the destination table of the columns named differently there
+    pub rename_columns: Option<HashMap<String, String>>,
     pub transform: Option<Vec<Transformation>>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+impl Table {
+    pub fn destination_table(&self) -> &str {
+        self.destination_table.as_ref().unwrap_or(&self.name)
+    }
+
+    /// Removes the excluded columns from the columns of the table
+    pub fn without_excluded(&self, columns: Vec<String>) -> Vec<String> {
+        match &self.exclude_columns {
+            Some(excluded) => columns
+                .into_iter()
+                .filter(|c| !excluded.contains(c))
+                .collect(),
+            None => columns,
+        }
+    }
+
+    pub fn destination_column<'a>(&'a self, column: &'a str) -> &'a str {
+        self.rename_columns
+            .as_ref()
+            .and_then(|renames| renames.get(column))
+            .map_or(column, |c| c.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Source {
-    pub connection_uri: String,
+    pub connection_uri: Secret,
     pub tables: Vec<Table>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Store {
     pub bucket: String,
-    pub aws_access_key_id: String,
-    pub aws_secret_access_key: String,
+    /// Without keys, S3 is accessed with the credentials of the environment
+    pub aws_access_key_id: Option<Secret>,
+    pub aws_secret_access_key: Option<Secret>,
+    /// Role Redshift assumes to access the bucket, instead of the keys
+    pub iam_role: Option<String>,
+}
+
+impl Store {
+    /// Authorization clause of the UNLOAD and COPY statements
+    pub fn authorization(&self) -> Result<String, String> {
+        if let Some(iam_role) = &self.iam_role {
+            return Ok(format!("IAM_ROLE '{}'", iam_role));
+        }
+        match (&self.aws_access_key_id, &self.aws_secret_access_key) {
+            (Some(key), Some(secret)) => Ok(format!(
+                "CREDENTIALS 'aws_access_key_id={};aws_secret_access_key={}'",
+                key.expose(),
+                secret.expose()
+            )),
+            _ => Err(
+                "store needs either an iam_role or aws_access_key_id and aws_secret_access_key"
+                    .to_string(),
+            ),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Destination {
-    pub connection_uri: String,
+    pub connection_uri: Secret,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RegexOptions {
     pub format: String,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ValueSource {
+    #[default]
+    Transformed,
+    Original,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct FromOptions {
     pub column: String,
+    #[serde(default, rename = "use")]
+    pub source: ValueSource,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct DateOptions {
     pub format: String,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RandomOptions {
-    pub range_start: usize,
-    pub range_end: usize,
+    #[serde(rename = "type", default)]
+    pub kind: RandomType,
+    pub range_start: Number,
+    pub range_end: Number,
+    /// Total number of digits of decimal values
+    pub precision: Option<u32>,
+    /// Number of digits after the decimal point
+    pub scale: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RandomType {
+    #[default]
+    Int,
+    Float,
+    Decimal,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+/// Numeric literal kept as written, so integers wider than 64 bits (given as
+/// quoted strings) and decimals don't lose precision
+#[derive(Debug, PartialEq, Serialize, Clone)]
+pub struct Number(pub String);
+
+impl JsonSchema for Number {
+    fn schema_name() -> String {
+        "Number".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(vec![InstanceType::Number, InstanceType::String].into()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl From<i64> for Number {
+    fn from(n: i64) -> Self {
+        Number(n.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Number {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct NumberVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for NumberVisitor {
+            type Value = Number;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a number")
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Number, E> {
+                Ok(Number(v.to_string()))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Number, E> {
+                Ok(Number(v.to_string()))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Number, E> {
+                Ok(Number(v.to_string()))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Number, E> {
+                Ok(Number(v.trim().to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(NumberVisitor)
+    }
+}
+
+impl Number {
+    pub fn to_int(&self) -> Result<i128, String> {
+        self.0
+            .parse()
+            .map_err(|_| format!("{} is not a valid integer", self.0))
+    }
+
+    pub fn to_float(&self) -> Result<f64, String> {
+        self.0
+            .parse()
+            .map_err(|_| format!("{} is not a valid number", self.0))
+    }
+
+    /// Integer representation of the number multiplied by `10^scale`
+    pub fn to_scaled(&self, scale: u32) -> Result<i128, String> {
+        let invalid = || format!("{} is not a valid decimal with scale {}", self.0, scale);
+        let (int, frac) = self.0.split_once('.').unwrap_or((&self.0, ""));
+        if frac.len() > scale as usize || !frac.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        format!("{}{:0<width$}", int, frac, width = scale as usize)
+            .parse()
+            .map_err(|_| invalid())
+    }
+}
+
+impl RandomOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        let ordered = match self.kind {
+            RandomType::Int => self.range_start.to_int()? < self.range_end.to_int()?,
+            RandomType::Float => self.range_start.to_float()? < self.range_end.to_float()?,
+            RandomType::Decimal => {
+                let scale = self.scale.unwrap_or(0);
+                let start = self.range_start.to_scaled(scale)?;
+                let end = self.range_end.to_scaled(scale)?;
+                if let Some(precision) = self.precision {
+                    let digits = |n: i128| n.unsigned_abs().to_string().len() as u32;
+                    if precision < scale || digits(start).max(digits(end)) > precision {
+                        return Err(format!(
+                            "random range {}..{} doesn't fit in precision {} with scale {}",
+                            self.range_start.0, self.range_end.0, precision, scale
+                        ));
+                    }
+                }
+                start < end
+            }
+        };
+
+        if !ordered {
+            return Err(format!(
+                "random range_start ({}) must be lower than range_end ({})",
+                self.range_start.0, self.range_end.0
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct StaticOptions {
     pub value: String,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RandomValueOptions {
+    #[serde(default)]
     pub values: Vec<String>,
+    pub weights: Option<Vec<f64>>,
+    /// Query returning value and count pairs used as values and weights
+    pub sample: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct TemplateOptions {
+    pub template: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct LookupOptions {
+    pub file: String,
+    pub fallback: Option<Box<TransformerType>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct PathRule {
+    pub path: String,
+    #[serde(flatten)]
+    pub transformer: TransformerType,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct PathOptions {
+    pub paths: Vec<PathRule>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct XmlOptions {
+    pub paths: Vec<PathRule>,
+    /// Namespace URIs by the prefix the paths use for them
+    pub namespaces: Option<HashMap<String, String>>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+fn path_transformers(paths: &[PathRule]) -> Vec<(String, Box<dyn Transformer>)> {
+    paths
+        .iter()
+        .map(|rule| (rule.path.clone(), rule.transformer.transformer()))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct KvRule {
+    pub key: String,
+    #[serde(flatten)]
+    pub transformer: TransformerType,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct KvOptions {
+    pub keys: Vec<KvRule>,
+    /// Separates the key=value pairs from each other
+    #[serde(default = "default_pair_separator")]
+    pub pair_separator: String,
+    /// Separates each key from its value
+    #[serde(default = "default_key_separator")]
+    pub key_separator: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct EachOptions {
+    /// Splits the value on a delimiter instead of reading an array literal
+    pub delimiter: Option<String>,
+    #[serde(flatten)]
+    pub transformer: Box<TransformerType>,
+}
+
+fn default_pair_separator() -> String {
+    ";".to_string()
+}
+
+fn default_key_separator() -> String {
+    "=".to_string()
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum NoiseDistribution {
+    #[default]
+    Gaussian,
+    Laplace,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct NoiseOptions {
+    #[serde(default)]
+    pub distribution: NoiseDistribution,
+    pub scale: f64,
+    /// Scale is a fraction of the original value instead of an absolute amount
+    #[serde(default)]
+    pub relative: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Decimal places of the result, defaults to the ones of the original value
+    pub precision: Option<usize>,
+}
+
+impl NoiseOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.scale < 0.0 || self.scale.is_nan() {
+            return Err(format!("noise scale ({}) can't be negative", self.scale));
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(format!(
+                    "noise min ({}) must not be greater than max ({})",
+                    min, max
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum MaskPreserve {
+    Punctuation,
+    Case,
+    Domain,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct MaskOptions {
+    #[serde(default)]
+    pub keep_first: usize,
+    #[serde(default)]
+    pub keep_last: usize,
+    /// Character used for masked positions, random letters and digits when unset
+    pub mask_char: Option<char>,
+    #[serde(default)]
+    pub preserve: Vec<MaskPreserve>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct FpeOptions {
+    /// Hex encoded AES key
+    pub key: Secret,
+    #[serde(default = "FpeOptions::default_alphabet")]
+    pub alphabet: String,
+    #[serde(default)]
+    pub tweak: String,
+}
+
+impl FpeOptions {
+    fn default_alphabet() -> String {
+        "0123456789".to_string()
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "fr_FR", alias = "fr-FR")]
+    FrFr,
+    #[serde(rename = "pt_BR", alias = "pt-BR")]
+    PtBr,
+    #[serde(rename = "ar_SA", alias = "ar-SA")]
+    ArSa,
+    #[serde(rename = "ja_JP", alias = "ja-JP")]
+    JaJp,
+    #[serde(rename = "zh_CN", alias = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "zh_TW", alias = "zh-TW")]
+    ZhTw,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct LocaleOptions {
+    pub locale: Option<Locale>,
+}
+
+impl LocaleOptions {
+    fn locale(options: &Option<LocaleOptions>) -> Locale {
+        options
+            .as_ref()
+            .and_then(|o| o.locale.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct LoremOptions {
+    /// Number of words, defaults to 5
+    pub words: Option<usize>,
+    /// Number of sentences, takes precedence over `words`
+    pub sentences: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RedactDetector {
+    Email,
+    Phone,
+    Ssn,
+    Date,
+}
+
+impl RedactDetector {
+    pub fn all() -> Vec<RedactDetector> {
+        vec![
+            RedactDetector::Email,
+            RedactDetector::Ssn,
+            RedactDetector::Phone,
+            RedactDetector::Date,
+        ]
+    }
+
+    pub fn pattern(&self) -> &str {
+        match self {
+            RedactDetector::Email => r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+            RedactDetector::Phone => r"(\(\d{3}\)\s?|\b\d{3}[-. ])\d{3}[-. ]\d{4}\b",
+            RedactDetector::Ssn => r"\b\d{3}-\d{2}-\d{4}\b",
+            RedactDetector::Date => r"\b(\d{4}-\d{2}-\d{2}|\d{1,2}/\d{1,2}/\d{2,4})\b",
+        }
+    }
+
+    pub fn placeholder(&self) -> &str {
+        match self {
+            RedactDetector::Email => "EMAIL",
+            RedactDetector::Phone => "PHONE",
+            RedactDetector::Ssn => "SSN",
+            RedactDetector::Date => "DATE",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct RedactPattern {
+    pub name: String,
+    pub regex: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RedactReplacement {
+    #[default]
+    Placeholder,
+    Fake,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct RedactOptions {
+    /// Built-in detectors to run, all of them by default
+    pub detectors: Option<Vec<RedactDetector>>,
+    #[serde(default)]
+    pub patterns: Vec<RedactPattern>,
+    /// Columns holding names to be redacted when found in the text
+    #[serde(default)]
+    pub name_columns: Vec<String>,
+    #[serde(default)]
+    pub replace: RedactReplacement,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct IdentifierOptions {
+    /// Derive the generated value from the original one
+    #[serde(default)]
+    pub deterministic: bool,
+    /// Secret the deterministic values are derived with, so they can't be
+    /// recomputed from a guessed original value
+    pub key: Option<Secret>,
+}
+
+impl IdentifierOptions {
+    /// Key the values are derived with, when they are deterministic
+    fn key(options: &Option<IdentifierOptions>) -> Option<&str> {
+        options
+            .as_ref()
+            .filter(|o| o.deterministic)
+            .and_then(|o| o.key.as_ref())
+            .map(|k| k.expose())
+    }
+
+    fn validate(options: &Option<IdentifierOptions>) -> Result<(), String> {
+        match options {
+            Some(options) if options.deterministic && options.key.is_none() => {
+                Err("deterministic identifiers need a secret key".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 #[serde(tag = "transformer", content = "properties")]
 pub enum TransformerType {
@@ -115,21 +700,47 @@ pub enum TransformerType {
     Sequence,
     Reverse,
     Regex(RegexOptions),
-    FirstName,
-    LastName,
-    Email,
-    Street,
-    City,
-    State,
-    ZipCode,
+    FirstName(Option<LocaleOptions>),
+    LastName(Option<LocaleOptions>),
+    Email(Option<LocaleOptions>),
+    Street(Option<LocaleOptions>),
+    City(Option<LocaleOptions>),
+    State(Option<LocaleOptions>),
+    ZipCode(Option<LocaleOptions>),
     From(FromOptions),
     Date(DateOptions),
     Random(RandomOptions),
     Static(StaticOptions),
     RandomValue(RandomValueOptions),
+    Template(TemplateOptions),
+    Lookup(LookupOptions),
+    Noise(NoiseOptions),
+    Mask(MaskOptions),
+    Fpe(FpeOptions),
+    Npi(Option<IdentifierOptions>),
+    CreditCard(Option<IdentifierOptions>),
+    Ssn(Option<IdentifierOptions>),
+    Phone(Option<IdentifierOptions>),
+    PhoneNumber(Option<LocaleOptions>),
+    FullName(Option<LocaleOptions>),
+    CompanyName(Option<LocaleOptions>),
+    SecondaryAddress(Option<LocaleOptions>),
+    Username(Option<LocaleOptions>),
+    JobTitle(Option<LocaleOptions>),
+    Ipv4,
+    Ipv6,
+    MacAddress,
+    Url,
+    Uuid,
+    Lorem(Option<LoremOptions>),
+    Redact(RedactOptions),
+    Json(PathOptions),
+    Xml(XmlOptions),
+    Kv(KvOptions),
+    Each(EachOptions),
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 #[serde(tag = "task", content = "properties")]
 pub enum TaskType {
@@ -142,48 +753,747 @@ impl TransformerType {
         match self {
             TransformerType::Null => Box::new(NullTransformer::default()),
             TransformerType::Reverse => Box::new(ReverseTransformer::default()),
-            TransformerType::FirstName => Box::new(FirstNameTransformer::default()),
-            TransformerType::LastName => Box::new(LastNameTransformer::default()),
+            TransformerType::FirstName(options) => {
+                Box::new(FirstNameTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::LastName(options) => {
+                Box::new(LastNameTransformer::new(&LocaleOptions::locale(options)))
+            }
             TransformerType::Sequence => Box::new(SequenceTransformer::default()),
-            TransformerType::Email => Box::new(EmailTransformer::default()),
-            TransformerType::Street => Box::new(StreetTransformer::default()),
-            TransformerType::City => Box::new(CityTransformer::default()),
-            TransformerType::State => Box::new(StateTransformer::default()),
-            TransformerType::ZipCode => Box::new(ZipCodeTransformer::default()),
+            TransformerType::Email(options) => {
+                Box::new(EmailTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::Street(options) => {
+                Box::new(StreetTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::City(options) => {
+                Box::new(CityTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::State(options) => {
+                Box::new(StateTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::ZipCode(options) => {
+                Box::new(ZipCodeTransformer::new(&LocaleOptions::locale(options)))
+            }
             TransformerType::Regex(options) => Box::new(RegexTransformer::new(&options.format)),
-            TransformerType::From(options) => Box::new(FromTransformer::new(&options.column)),
+            TransformerType::From(options) => {
+                Box::new(FromTransformer::new(&options.column, &options.source))
+            }
             TransformerType::Date(options) => Box::new(DateTransformer::new(&options.format)),
             TransformerType::Random(options) => Box::new(RandomTransformer::new(
+                &options.kind,
                 &options.range_start,
                 &options.range_end,
+                options.scale,
             )),
             TransformerType::Static(options) => Box::new(StaticTransformer::new(&options.value)),
             TransformerType::RandomValue(options) => {
-                Box::new(RandomValueTransformer::new(&options.values))
+                Box::new(RandomValueTransformer::new(&options.values, &options.weights).unwrap())
+            }
+            TransformerType::Template(options) => {
+                Box::new(TemplateTransformer::new(&options.template).unwrap())
+            }
+            TransformerType::Lookup(options) => Box::new(
+                LookupTransformer::new(
+                    &options.file,
+                    options.fallback.as_ref().map(|f| f.transformer()),
+                )
+                .unwrap(),
+            ),
+            TransformerType::Noise(options) => Box::new(NoiseTransformer::new(
+                &options.distribution,
+                options.scale,
+                options.relative,
+                options.min,
+                options.max,
+                options.precision,
+            )),
+            TransformerType::Mask(options) => Box::new(MaskTransformer::new(
+                options.keep_first,
+                options.keep_last,
+                options.mask_char,
+                &options.preserve,
+            )),
+            TransformerType::Fpe(options) => Box::new(
+                FpeTransformer::new(options.key.expose(), &options.alphabet, &options.tweak)
+                    .unwrap(),
+            ),
+            TransformerType::Npi(options) => {
+                Box::new(NpiTransformer::new(IdentifierOptions::key(options)))
             }
+            TransformerType::CreditCard(options) => {
+                Box::new(CreditCardTransformer::new(IdentifierOptions::key(options)))
+            }
+            TransformerType::Ssn(options) => {
+                Box::new(SsnTransformer::new(IdentifierOptions::key(options)))
+            }
+            TransformerType::Phone(options) => {
+                Box::new(PhoneTransformer::new(IdentifierOptions::key(options)))
+            }
+            TransformerType::PhoneNumber(options) => {
+                Box::new(PhoneNumberTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::FullName(options) => {
+                Box::new(FullNameTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::CompanyName(options) => {
+                Box::new(CompanyNameTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::SecondaryAddress(options) => Box::new(
+                SecondaryAddressTransformer::new(&LocaleOptions::locale(options)),
+            ),
+            TransformerType::Username(options) => {
+                Box::new(UsernameTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::JobTitle(options) => {
+                Box::new(JobTitleTransformer::new(&LocaleOptions::locale(options)))
+            }
+            TransformerType::Ipv4 => Box::new(Ipv4Transformer::default()),
+            TransformerType::Ipv6 => Box::new(Ipv6Transformer::default()),
+            TransformerType::MacAddress => Box::new(MacAddressTransformer::default()),
+            TransformerType::Url => Box::new(UrlTransformer::default()),
+            TransformerType::Uuid => Box::new(UuidTransformer::default()),
+            TransformerType::Lorem(options) => Box::new(LoremTransformer::new(
+                options.as_ref().and_then(|o| o.words).unwrap_or(5),
+                options.as_ref().and_then(|o| o.sentences),
+            )),
+            TransformerType::Redact(options) => Box::new(
+                RedactTransformer::new(
+                    &options
+                        .detectors
+                        .clone()
+                        .unwrap_or_else(RedactDetector::all),
+                    &options.patterns,
+                    &options.name_columns,
+                    &options.replace,
+                )
+                .unwrap(),
+            ),
+            TransformerType::Json(options) => {
+                Box::new(JsonTransformer::new(path_transformers(&options.paths)))
+            }
+            TransformerType::Xml(options) => Box::new(
+                XmlTransformer::new(
+                    path_transformers(&options.paths),
+                    options.namespaces.clone().unwrap_or_default(),
+                )
+                .unwrap(),
+            ),
+            TransformerType::Kv(options) => Box::new(KvTransformer::new(
+                options
+                    .keys
+                    .iter()
+                    .map(|rule| (rule.key.clone(), rule.transformer.transformer()))
+                    .collect(),
+                &options.pair_separator,
+                &options.key_separator,
+            )),
+            TransformerType::Each(options) => Box::new(EachTransformer::new(
+                options.transformer.transformer(),
+                options.delimiter.clone(),
+            )),
         }
     }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TransformerType::Random(options) => options.validate(),
+            TransformerType::Noise(options) => options.validate(),
+            TransformerType::Fpe(options) => {
+                FpeTransformer::new(options.key.expose(), &options.alphabet, &options.tweak)
+                    .map(|_| ())
+            }
+            TransformerType::Template(options) => {
+                TemplateTransformer::new(&options.template).map(|_| ())
+            }
+            TransformerType::Npi(options)
+            | TransformerType::CreditCard(options)
+            | TransformerType::Ssn(options)
+            | TransformerType::Phone(options) => IdentifierOptions::validate(options),
+            // Sampled values and weights are only known once the query ran
+            TransformerType::RandomValue(options) if options.sample.is_none() => {
+                RandomValueTransformer::new(&options.values, &options.weights).map(|_| ())
+            }
+            TransformerType::Lookup(options) => {
+                LookupTransformer::new(&options.file, None)?;
+                match &options.fallback {
+                    Some(fallback) => fallback.validate(),
+                    None => Ok(()),
+                }
+            }
+            TransformerType::Redact(options) => {
+                RedactTransformer::new(&[], &options.patterns, &[], &options.replace).map(|_| ())
+            }
+            TransformerType::Json(options) => {
+                for rule in &options.paths {
+                    jsonpath_lib::Compiled::compile(&rule.path)
+                        .map_err(|e| format!("json path {}: {}", rule.path, e))?;
+                    rule.transformer.validate()?;
+                }
+                Ok(())
+            }
+            TransformerType::Xml(options) => {
+                for rule in &options.paths {
+                    rule.transformer.validate()?;
+                }
+                XmlTransformer::new(
+                    path_transformers(&options.paths),
+                    options.namespaces.clone().unwrap_or_default(),
+                )
+                .map(|_| ())
+            }
+            TransformerType::Kv(options) => {
+                for rule in &options.keys {
+                    rule.transformer.validate()?;
+                }
+                Ok(())
+            }
+            TransformerType::Each(options) => options.transformer.validate(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the transformer always produces the same value for a row, so
+    /// generating it again can't get a different one
+    pub fn is_deterministic(&self) -> bool {
+        match self {
+            TransformerType::Reverse
+            | TransformerType::Sequence
+            | TransformerType::Static(_)
+            | TransformerType::From(_)
+            | TransformerType::Lookup(_)
+            | TransformerType::Fpe(_) => true,
+            TransformerType::Mask(options) => options.mask_char.is_some(),
+            TransformerType::Npi(options)
+            | TransformerType::CreditCard(options)
+            | TransformerType::Ssn(options)
+            | TransformerType::Phone(options) => options.as_ref().is_some_and(|o| o.deterministic),
+            _ => false,
+        }
+    }
+
+    /// Options of the random value transformers with a `sample` query, nested
+    /// ones included
+    pub fn sampled_mut(&mut self) -> Vec<&mut RandomValueOptions> {
+        match self {
+            TransformerType::RandomValue(options) if options.sample.is_some() => vec![options],
+            TransformerType::Lookup(LookupOptions {
+                fallback: Some(fallback),
+                ..
+            }) => fallback.sampled_mut(),
+            TransformerType::Json(PathOptions { paths })
+            | TransformerType::Xml(XmlOptions { paths, .. }) => paths
+                .iter_mut()
+                .flat_map(|rule| rule.transformer.sampled_mut())
+                .collect(),
+            TransformerType::Kv(options) => options
+                .keys
+                .iter_mut()
+                .flat_map(|rule| rule.transformer.sampled_mut())
+                .collect(),
+            TransformerType::Each(options) => options.transformer.sampled_mut(),
+            _ => vec![],
+        }
+    }
+
+    /// Sets the locale of faker transformers that don't have one
+    pub fn set_default_locale(&mut self, locale: &Locale) {
+        match self {
+            TransformerType::FirstName(options)
+            | TransformerType::LastName(options)
+            | TransformerType::Email(options)
+            | TransformerType::Street(options)
+            | TransformerType::City(options)
+            | TransformerType::State(options)
+            | TransformerType::ZipCode(options)
+            | TransformerType::PhoneNumber(options)
+            | TransformerType::FullName(options)
+            | TransformerType::CompanyName(options)
+            | TransformerType::SecondaryAddress(options)
+            | TransformerType::Username(options)
+            | TransformerType::JobTitle(options) => {
+                let options = options.get_or_insert_with(LocaleOptions::default);
+                options.locale.get_or_insert_with(|| locale.clone());
+            }
+            TransformerType::Lookup(LookupOptions {
+                fallback: Some(fallback),
+                ..
+            }) => fallback.set_default_locale(locale),
+            TransformerType::Json(PathOptions { paths })
+            | TransformerType::Xml(XmlOptions { paths, .. }) => {
+                for rule in paths {
+                    rule.transformer.set_default_locale(locale);
+                }
+            }
+            TransformerType::Kv(options) => {
+                for rule in &mut options.keys {
+                    rule.transformer.set_default_locale(locale);
+                }
+            }
+            TransformerType::Each(options) => options.transformer.set_default_locale(locale),
+            _ => {}
+        }
+    }
+
+    /// Columns whose transformed values this transformer reads
+    pub fn dependencies(&self) -> Vec<String> {
+        match self {
+            TransformerType::From(options) if options.source == ValueSource::Transformed => {
+                vec![options.column.clone()]
+            }
+            TransformerType::Template(options) => {
+                TemplateTransformer::referenced_names(&options.template)
+            }
+            TransformerType::Lookup(options) => match &options.fallback {
+                Some(fallback) => fallback.dependencies(),
+                None => vec![],
+            },
+            TransformerType::Redact(options) if options.replace == RedactReplacement::Fake => {
+                options.name_columns.clone()
+            }
+            TransformerType::Json(PathOptions { paths })
+            | TransformerType::Xml(XmlOptions { paths, .. }) => paths
+                .iter()
+                .flat_map(|rule| rule.transformer.dependencies())
+                .collect(),
+            TransformerType::Kv(options) => options
+                .keys
+                .iter()
+                .flat_map(|rule| rule.transformer.dependencies())
+                .collect(),
+            TransformerType::Each(options) => options.transformer.dependencies(),
+            _ => vec![],
+        }
+    }
+}
+
+impl Condition {
+    /// Checks the pattern of the condition
+    pub fn validate(&self) -> Result<(), String> {
+        self.regex().map(|_| ())
+    }
+
+    /// Binds the condition to the position of its column in rows with the
+    /// given columns and compiles its pattern
+    pub fn compile(&self, columns: &[String]) -> Result<RowCondition, String> {
+        let index = columns
+            .iter()
+            .position(|c| *c == self.column)
+            .ok_or_else(|| format!("condition column {} is not extracted", self.column))?;
+
+        Ok(RowCondition {
+            index,
+            predicate: self.predicate.clone(),
+            regex: self.regex()?,
+        })
+    }
+
+    fn regex(&self) -> Result<Option<Regex>, String> {
+        match &self.predicate {
+            Predicate::Matches(pattern) => Regex::new(pattern)
+                .map(Some)
+                .map_err(|e| format!("condition pattern {}: {}", pattern, e)),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Condition compiled for the columns of a table
+pub struct RowCondition {
+    index: usize,
+    predicate: Predicate,
+    regex: Option<Regex>,
+}
+
+impl RowCondition {
+    /// Evaluates the condition against the original values of the row
+    pub fn matches(&self, row: &[&str]) -> bool {
+        let value = row[self.index];
+
+        match &self.predicate {
+            Predicate::Equals(expected) => value == expected,
+            Predicate::In(values) => values.iter().any(|v| v == value),
+            Predicate::Matches(_) => self.regex.as_ref().is_some_and(|r| r.is_match(value)),
+            Predicate::IsNull(is_null) => value.is_empty() == *is_null,
+        }
+    }
+}
+
+/// Interpolates `${VAR}`, `${VAR:-default}` and `${VAR:?message}` with
+/// environment variables, `${file:path}` with the contents of a file and
+/// `${cmd:command}` with the output of a shell command, `$$` being a literal
+/// `$`. Postgres dollar-quote tags like `$body$` are kept as they are, while
+/// `$$` quotes have to be written `$$$$`. Every value that can't be resolved
+/// is reported in the error, as well as any `$VAR` without braces, which
+/// isn't interpolated.
+fn replace_env_vars(s: &str) -> Result<String, String> {
+    let re = Regex::new(
+        r"\$(?:(?P<escape>\$)|(?P<tag>[A-Za-z_][A-Za-z0-9_]*)\$|(?P<bare>[A-Za-z_][A-Za-z0-9_]*)|\{(?:(?P<source>file|cmd):(?P<arg>[^}]*)|(?P<name>[A-Za-z_][A-Za-z0-9_]*)(?::(?P<op>[-?])(?P<word>[^}]*))?)\})",
+    )
+    .unwrap();
+    let mut unresolved = vec![];
+    let mut bare = vec![];
+
+    let res = re.replace_all(s, |caps: &Captures| {
+        if caps.name("escape").is_some() {
+            return "$".to_string();
+        }
+        if caps.name("tag").is_some() {
+            return caps[0].to_string();
+        }
+        if caps.name("bare").is_some() {
+            bare.push(caps[0].to_string());
+            return caps[0].to_string();
+        }
+        if let Some(source) = caps.name("source") {
+            let arg = caps["arg"].trim();
+            let value = match source.as_str() {
+                "file" => read_secret_file(arg),
+                _ => run_secret_command(arg),
+            };
+            return value.unwrap_or_else(|e| {
+                unresolved.push(format!("{}:{} ({})", source.as_str(), arg, e));
+                "".to_string()
+            });
+        }
+
+        let name = &caps["name"];
+        let value = env::var(name).ok();
+        match (caps.name("op").map(|m| m.as_str()), value) {
+            (Some("-"), value) => match value.filter(|v| !v.is_empty()) {
+                Some(value) => value,
+                None => caps["word"].to_string(),
+            },
+            (_, Some(value)) => value,
+            (Some("?"), None) => {
+                unresolved.push(format!("{} ({})", name, &caps["word"]));
+                "".to_string()
+            }
+            (_, None) => {
+                unresolved.push(name.to_string());
+                "".to_string()
+            }
+        }
+    });
+
+    if !unresolved.is_empty() {
+        return Err(format!("unresolved variables: {}", unresolved.join(", ")));
+    }
+    if !bare.is_empty() {
+        return Err(format!(
+            "variables must be written as ${{NAME}}, or $$ for a literal $: {}",
+            bare.join(", ")
+        ));
+    }
+    Ok(res.to_string())
+}
+
+/// Interpolates the string values of the parsed document, so interpolated
+/// values can't change its structure and comments are left out
+fn interpolate(value: &mut Value, errors: &mut Vec<String>) {
+    match value {
+        Value::String(s) => match replace_env_vars(s) {
+            Ok(replaced) => {
+                let single = Regex::new(r"^\$\{[^}]*\}$").unwrap().is_match(s);
+                *value = match single {
+                    true => scalar(replaced),
+                    false => Value::String(replaced),
+                }
+            }
+            Err(e) => errors.push(e),
+        },
+        Value::Sequence(sequence) => sequence.iter_mut().for_each(|v| interpolate(v, errors)),
+        Value::Mapping(mapping) => mapping.iter_mut().for_each(|(_, v)| interpolate(v, errors)),
+        _ => {}
+    }
+}
+
+/// Reads a value that is a single reference as a YAML scalar, so numbers and
+/// booleans can come from variables. Values that would be read back
+/// differently, such as `0123` or `null`, are kept as strings.
+fn scalar(s: String) -> Value {
+    match serde_yaml::from_str::<Value>(&s) {
+        Ok(value @ (Value::Bool(_) | Value::Number(_)))
+            if serde_yaml::to_string(&value)
+                .is_ok_and(|yaml| yaml.trim_start_matches("---").trim() == s) =>
+        {
+            value
+        }
+        _ => Value::String(s),
+    }
+}
+
+fn read_secret_file(path: &str) -> Result<String, String> {
+    fs::read_to_string(path)
+        .map(|contents| contents.trim_end_matches(['\r', '\n']).to_string())
+        .map_err(|e| e.to_string())
+}
+
+fn run_secret_command(command: &str) -> Result<String, String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "{}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout)
+        .map(|stdout| stdout.trim_end_matches(['\r', '\n']).to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Reads a config file, interpolating variables and merging the files listed
+/// in its `include` under it
+fn load_yaml(file: &Path, stack: &mut Vec<PathBuf>) -> Result<Value, String> {
+    let path = file
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", file.display(), e))?;
+    if stack.contains(&path) {
+        return Err(format!("{} includes itself", file.display()));
+    }
+
+    let contents = fs::read_to_string(&path).map_err(|e| format!("{}: {}", file.display(), e))?;
+    let mut value = parse_yaml(&contents).map_err(|e| format!("{}: {}", file.display(), e))?;
+    let mut errors = vec![];
+    interpolate(&mut value, &mut errors);
+    if !errors.is_empty() {
+        return Err(format!("{}: {}", file.display(), errors.join("; ")));
+    }
+
+    let includes = match &mut value {
+        Value::Mapping(mapping) => mapping.remove(&Value::from("include")),
+        _ => None,
+    };
+    let includes: Vec<String> = match includes {
+        Some(includes) => serde_yaml::from_value(includes)
+            .map_err(|e| format!("{}: include: {}", file.display(), e))?,
+        None => return Ok(value),
+    };
+
+    stack.push(path.clone());
+    let mut merged = Value::Mapping(Mapping::new());
+    for include in includes {
+        let include = path.parent().unwrap().join(include);
+        merge_yaml(&mut merged, load_yaml(&include, stack)?);
+    }
+    stack.pop();
+
+    merge_yaml(&mut merged, value);
+    Ok(merged)
+}
+
+fn parse_yaml(s: &str) -> Result<Value, String> {
+    let mut value: Value = serde_yaml::from_str(s).map_err(|e| e.to_string())?;
+    resolve_merge_keys(&mut value);
+    Ok(value)
+}
+
+/// Applies `<<` merge keys, so anchored mappings can be reused and extended
+fn resolve_merge_keys(value: &mut Value) {
+    match value {
+        Value::Sequence(sequence) => sequence.iter_mut().for_each(resolve_merge_keys),
+        Value::Mapping(mapping) => {
+            for (_, child) in mapping.iter_mut() {
+                resolve_merge_keys(child);
+            }
+            let sources = match mapping.remove(&Value::from("<<")) {
+                Some(Value::Sequence(sources)) => sources,
+                Some(source) => vec![source],
+                None => return,
+            };
+            for source in sources {
+                if let Value::Mapping(source) = source {
+                    for (key, child) in source {
+                        if !mapping.contains_key(&key) {
+                            mapping.insert(key, child);
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Merges `overlay` into `base`. Mappings are merged key by key, while any
+/// other value, including sequences, replaces the base one.
+fn merge_yaml(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// JSON Schema of the configuration file
+pub fn schema() -> String {
+    let mut gen = SchemaGenerator::default();
+    let mut schema = gen.root_schema_for::<Config>();
+    // `include` is resolved while loading, before the config is deserialized
+    let include = gen.subschema_for::<Vec<String>>();
+    schema
+        .schema
+        .object()
+        .properties
+        .insert("include".to_string(), include);
+    schema.definitions.extend(gen.take_definitions());
+
+    let mut schema = serde_json::to_value(&schema).unwrap();
+    relax_optional_properties(&mut schema);
+    serde_json::to_string_pretty(&schema).unwrap()
 }
 
-fn replace_env_vars(s: &str) -> String {
-    let mut res = s.to_owned();
-    let mut vars: Vec<(String, String)> = env::vars().into_iter().collect();
-    vars.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+/// Optional transformer properties are listed as required, but serde accepts
+/// them missing, so drop required keys whose schema allows null
+fn relax_optional_properties(schema: &mut serde_json::Value) {
+    use serde_json::Value as Json;
 
-    for (name, val) in vars {
-        res = res.replace(&format!("${}", name), &val).to_owned();
+    match schema {
+        Json::Object(object) => {
+            let nullable: Vec<String> = match object.get("properties") {
+                Some(Json::Object(properties)) => properties
+                    .iter()
+                    .filter(|(_, property)| {
+                        property["anyOf"]
+                            .as_array()
+                            .is_some_and(|any| any.iter().any(|s| s["type"] == "null"))
+                    })
+                    .map(|(name, _)| name.clone())
+                    .collect(),
+                _ => vec![],
+            };
+            if let Some(Json::Array(required)) = object.get_mut("required") {
+                required.retain(|name| !nullable.iter().any(|n| name == n.as_str()));
+            }
+            object.values_mut().for_each(relax_optional_properties);
+        }
+        Json::Array(array) => array.iter_mut().for_each(relax_optional_properties),
+        _ => {}
     }
-    res.to_owned()
 }
 
 impl Config {
-    pub fn new(file: &str) -> Self {
-        let config_str = replace_env_vars(&fs::read_to_string(file).unwrap());
-        Self::new_from_str(&config_str)
+    pub fn new(file: &str) -> Result<Self, String> {
+        Self::from_yaml(load_yaml(Path::new(file), &mut vec![])?)
     }
 
+    #[cfg(test)]
     pub fn new_from_str(s: &str) -> Self {
-        serde_yaml::from_str(s).unwrap()
+        Self::from_yaml(parse_yaml(s).unwrap()).unwrap()
+    }
+
+    fn from_yaml(value: Value) -> Result<Self, String> {
+        // Deserialized from text, so scalars read from variables also fill
+        // string fields
+        let yaml = serde_yaml::to_string(&value).map_err(|e| e.to_string())?;
+        let mut config: Config = serde_yaml::from_str(&yaml).map_err(|e| e.to_string())?;
+        config.apply_profiles()?;
+        config.apply_locale();
+        Ok(config)
+    }
+
+    /// Adds the transformations of the table's profile for the columns the
+    /// table doesn't transform itself
+    fn apply_profiles(&mut self) -> Result<(), String> {
+        for table in self.source.tables.iter_mut() {
+            let name = match &table.transform_profile {
+                Some(name) => name,
+                None => continue,
+            };
+            let profile = self
+                .profiles
+                .as_ref()
+                .and_then(|profiles| profiles.get(name))
+                .ok_or_else(|| format!("{}: unknown transform_profile {}", table.name, name))?;
+
+            let own = table.transform.take().unwrap_or_default();
+            let mut transform: Vec<Transformation> = profile
+                .iter()
+                .filter(|t| !own.iter().any(|o| o.column == t.column))
+                .cloned()
+                .collect();
+            transform.extend(own);
+            table.transform = Some(transform);
+        }
+        Ok(())
+    }
+
+    fn apply_locale(&mut self) {
+        let locale = match &self.locale {
+            Some(locale) => locale.clone(),
+            None => return,
+        };
+        for table in self.source.tables.iter_mut() {
+            for transformation in table.transform.iter_mut().flatten() {
+                transformation.transformer.set_default_locale(&locale);
+                if let Some(otherwise) = &mut transformation.otherwise {
+                    otherwise.set_default_locale(&locale);
+                }
+            }
+        }
+    }
+
+    pub fn transformation(&self, table: &str, column: &str) -> Option<&Transformation> {
+        self.source
+            .tables
+            .iter()
+            .filter(|t| t.name == table)
+            .flat_map(|t| t.transform.iter().flatten())
+            .find(|t| t.column == column)
+    }
+
+    /// Checks transformer options that can't be expressed through the types
+    pub fn validate(&self) -> Result<(), String> {
+        self.store.authorization()?;
+        for table in &self.source.tables {
+            for transformation in table.transform.iter().flatten() {
+                if let Some(condition) = &transformation.when {
+                    condition
+                        .validate()
+                        .map_err(|e| format!("{}.{}: {}", table.name, transformation.column, e))?;
+                    if let Some(columns) = &table.columns {
+                        if !columns.contains(&condition.column) {
+                            return Err(format!(
+                                "{}.{}: condition column {} is not extracted",
+                                table.name, transformation.column, condition.column
+                            ));
+                        }
+                    }
+                }
+                let unique = transformation
+                    .unique
+                    .as_ref()
+                    .and_then(|u| u.options())
+                    .is_some();
+                let transformers =
+                    std::iter::once(&transformation.transformer).chain(&transformation.otherwise);
+                for transformer in transformers {
+                    transformer
+                        .validate()
+                        .map_err(|e| format!("{}.{}: {}", table.name, transformation.column, e))?;
+                    if unique && transformer.is_deterministic() {
+                        return Err(format!(
+                            "{}.{}: unique can't be used with a deterministic transformer, it would never produce another value",
+                            table.name, transformation.column
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 }
 
@@ -195,7 +1505,7 @@ mod tests {
     fn test_config() {
         let str = indoc::indoc! {r#"
 source:
-  connection_uri: $DATABASE_URL
+  connection_uri: ${DATABASE_URL}
   tables:
     - name: patient_master_record
       generate: 100
@@ -252,11 +1562,11 @@ source:
 
 store:
   bucket: nw-data-transfer
-  aws_access_key_id: $AWS_ACCESS_KEY_ID
-  aws_secret_access_key: $AWS_SECRET_ACCESS_KEY
+  aws_access_key_id: ${AWS_ACCESS_KEY_ID}
+  aws_secret_access_key: ${AWS_SECRET_ACCESS_KEY}
 
 destination:
-  connection_uri: $EXP_DATABASE_URL
+  connection_uri: ${EXP_DATABASE_URL}
 
 postprocess:
   - name: Generate results PDF
@@ -270,9 +1580,11 @@ postprocess:
           JOIN "orders_tests" ot ON ot.order_id = o.id
           JOIN "patients" p ON p.id = o.patient_id
       bucket: nw-pdf
+      font: fonts/Helvetica.ttf
+      contents: "{{first_name}} {{last_name}}"
       file_name: ""
-      aws_access_key_id: $AWS_ACCESS_KEY_ID
-      aws_secret_access_key: $AWS_SECRET_ACCESS_KEY
+      aws_access_key_id: ${AWS_ACCESS_KEY_ID}
+      aws_secret_access_key: ${AWS_SECRET_ACCESS_KEY}
         "#};
 
         let config = Config::new_from_str(str);
@@ -284,7 +1596,590 @@ postprocess:
     fn test_env_vars() {
         env::set_var("TEST_ENV_VAR", "small");
         env::set_var("TEST_ENV_VAR_1", "large");
-        let res = replace_env_vars("env=$TEST_ENV_VAR_1,database=$TEST_ENV_VAR");
-        assert_eq!(res, "env=large,database=small");
+        env::set_var("TEST_ENV_VAR_EMPTY", "");
+        let res = replace_env_vars("env=${TEST_ENV_VAR_1},database=${TEST_ENV_VAR}");
+        assert_eq!(res.unwrap(), "env=large,database=small");
+
+        let res = replace_env_vars(
+            "${TEST_ENV_VAR_EMPTY:-default},${TEST_ENV_VAR:-default},${TEST_ENV_VAR:?required}",
+        );
+        assert_eq!(res.unwrap(), "default,small,small");
+
+        let res = replace_env_vars("matches: '^\\d+$',price: $$10,raw: $$TEST_ENV_VAR");
+        assert_eq!(
+            res.unwrap(),
+            "matches: '^\\d+$',price: $10,raw: $TEST_ENV_VAR"
+        );
+
+        let res = replace_env_vars("uri: $TEST_ENV_VAR/$TEST_ENV_VAR_1,price: $10");
+        assert_eq!(
+            res.unwrap_err(),
+            "variables must be written as ${NAME}, or $$ for a literal $: $TEST_ENV_VAR, $TEST_ENV_VAR_1"
+        );
+
+        let res = replace_env_vars(
+            "DO $body$ BEGIN PERFORM '${TEST_ENV_VAR}'; END $body$; DO $$$$ SELECT 1 $$$$",
+        );
+        assert_eq!(
+            res.unwrap(),
+            "DO $body$ BEGIN PERFORM 'small'; END $body$; DO $$ SELECT 1 $$"
+        );
+
+        let res = replace_env_vars("${TEST_ENV_VAR_UNSET},${TEST_ENV_VAR_UNSET_2:?set the key}");
+        assert_eq!(
+            res.unwrap_err(),
+            "unresolved variables: TEST_ENV_VAR_UNSET, TEST_ENV_VAR_UNSET_2 (set the key)"
+        );
+    }
+
+    #[test]
+    fn test_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret = dir.path().join("secret");
+        fs::write(&secret, "file-secret\n").unwrap();
+        let marker = dir.path().join("marker");
+        env::set_var("TEST_SECRET_YAML", "p@ss: #word\n*x");
+
+        let file = dir.path().join("config.yml");
+        fs::write(
+            &file,
+            format!(
+                indoc::indoc! {r#"
+                    source:
+                      connection_uri: ${{file:{}}}
+                      tables: []
+                    store:
+                      bucket: bucket
+                      aws_access_key_id: ${{cmd:echo cmd-secret}}
+                      # aws_secret_access_key: ${{cmd:touch {}}}
+                      aws_secret_access_key: ${{TEST_SECRET_YAML}}
+                    destination:
+                      connection_uri: postgres://localhost
+                "#},
+                secret.display(),
+                marker.display()
+            ),
+        )
+        .unwrap();
+        let config = Config::new(file.to_str().unwrap()).unwrap();
+        assert_eq!(config.source.connection_uri.expose(), "file-secret");
+        assert_eq!(
+            config.store.aws_access_key_id.as_ref().unwrap().expose(),
+            "cmd-secret"
+        );
+        assert_eq!(
+            config
+                .store
+                .aws_secret_access_key
+                .as_ref()
+                .unwrap()
+                .expose(),
+            "p@ss: #word\n*x"
+        );
+        assert!(!marker.exists());
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("file-secret") && !debug.contains("cmd-secret"));
+        assert!(debug.contains("<redacted>"));
+
+        let res = replace_env_vars("${file:/nonexistent},${cmd:exit 3}");
+        assert!(res
+            .unwrap_err()
+            .starts_with("unresolved variables: file:/nonexistent ("));
+    }
+
+    #[test]
+    fn test_typed_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        env::set_var("TEST_TYPED_LIMIT", "100");
+        env::set_var("TEST_TYPED_PASSWORD", "0123");
+
+        let file = dir.path().join("config.yml");
+        fs::write(
+            &file,
+            indoc::indoc! {r#"
+                source:
+                  connection_uri: postgres://localhost
+                  tables:
+                    - name: users
+                      limit: ${TEST_TYPED_LIMIT}
+                      generate: ${TEST_TYPED_GENERATE:-10}
+                store:
+                  bucket: bucket
+                  aws_access_key_id: ${TEST_TYPED_LIMIT}
+                  aws_secret_access_key: ${TEST_TYPED_PASSWORD}
+                destination:
+                  connection_uri: postgres://localhost
+            "#},
+        )
+        .unwrap();
+        let config = Config::new(file.to_str().unwrap()).unwrap();
+        assert_eq!(config.source.tables[0].limit, Some(100));
+        assert_eq!(config.source.tables[0].generate, Some(10));
+        assert_eq!(
+            config.store.aws_access_key_id.as_ref().unwrap().expose(),
+            "100"
+        );
+        assert_eq!(
+            config
+                .store
+                .aws_secret_access_key
+                .as_ref()
+                .unwrap()
+                .expose(),
+            "0123"
+        );
+    }
+
+    #[test]
+    fn test_conditional_transformation_config() {
+        let str = indoc::indoc! {r#"
+            column: email
+            transformer: static
+            properties:
+              value: test@example.com
+            when:
+              column: email
+              matches: '@ourcompany\.com$'
+            else:
+              transformer: email
+        "#};
+
+        let transformation: Transformation = serde_yaml::from_str(str).unwrap();
+        assert_eq!(
+            transformation.when,
+            Some(Condition {
+                column: "email".to_string(),
+                predicate: Predicate::Matches("@ourcompany\\.com$".to_string()),
+            })
+        );
+        assert_eq!(transformation.otherwise, Some(TransformerType::Email(None)));
+    }
+
+    #[test]
+    fn test_random_options() {
+        let parse = |s: &str| serde_yaml::from_str::<TransformerType>(s).unwrap();
+
+        let valid = parse(indoc::indoc! {r#"
+            transformer: random
+            properties:
+              type: decimal
+              range_start: -10.5
+              range_end: 12.50
+              precision: 4
+              scale: 2
+        "#});
+        assert_eq!(valid.validate(), Ok(()));
+
+        let wide = parse(indoc::indoc! {r#"
+            transformer: random
+            properties:
+              range_start: 0
+              range_end: "99999999999999999999999999999999999999"
+        "#});
+        assert_eq!(wide.validate(), Ok(()));
+
+        let reversed = parse(indoc::indoc! {r#"
+            transformer: random
+            properties:
+              type: float
+              range_start: 10
+              range_end: -10
+        "#});
+        assert!(reversed.validate().is_err());
+
+        let overflow = parse(indoc::indoc! {r#"
+            transformer: random
+            properties:
+              type: decimal
+              range_start: 0
+              range_end: 1000
+              precision: 4
+              scale: 2
+        "#});
+        assert!(overflow.validate().is_err());
+    }
+
+    #[test]
+    fn test_noise_options() {
+        let parse = |s: &str| serde_yaml::from_str::<TransformerType>(s).unwrap();
+
+        let valid = parse(indoc::indoc! {r#"
+            transformer: noise
+            properties:
+              scale: 5
+              min: 0
+              max: 100
+        "#});
+        assert_eq!(valid.validate(), Ok(()));
+
+        let negative = parse(indoc::indoc! {r#"
+            transformer: noise
+            properties:
+              scale: -5
+        "#});
+        assert!(negative.validate().is_err());
+
+        let inverted = parse(indoc::indoc! {r#"
+            transformer: noise
+            properties:
+              scale: 0.1
+              relative: true
+              min: 100
+              max: 0
+        "#});
+        assert!(inverted.validate().is_err());
+    }
+
+    #[test]
+    fn test_random_value_options() {
+        let parse = |s: &str| serde_yaml::from_str::<TransformerType>(s).unwrap();
+
+        let sampled = parse(indoc::indoc! {r#"
+            transformer: random-value
+            properties:
+              sample: select status, count(*) from orders group by 1
+        "#});
+        assert_eq!(sampled.validate(), Ok(()));
+
+        let mut nested = parse(indoc::indoc! {r#"
+            transformer: json
+            properties:
+              paths:
+                - path: $.status
+                  transformer: random-value
+                  properties:
+                    sample: select status, count(*) from orders group by 1
+                - path: $.tags
+                  transformer: each
+                  properties:
+                    transformer: random-value
+                    properties:
+                      sample: select tag, count(*) from tags group by 1
+        "#});
+        assert_eq!(nested.validate(), Ok(()));
+        assert_eq!(nested.sampled_mut().len(), 2);
+
+        let missing_weight = parse(indoc::indoc! {r#"
+            transformer: random-value
+            properties:
+              values: [completed, cancelled]
+              weights: [1]
+        "#});
+        assert!(missing_weight.validate().is_err());
+    }
+
+    #[test]
+    fn test_identifier_options() {
+        let parse = |s: &str| serde_yaml::from_str::<TransformerType>(s).unwrap();
+
+        let keyed = parse(indoc::indoc! {r#"
+            transformer: npi
+            properties:
+              deterministic: true
+              key: s3cr3t
+        "#});
+        assert_eq!(keyed.validate(), Ok(()));
+
+        let unkeyed = parse(indoc::indoc! {r#"
+            transformer: ssn
+            properties:
+              deterministic: true
+        "#});
+        assert!(unkeyed.validate().is_err());
+    }
+
+    #[test]
+    fn test_template_options() {
+        let template = |t: &str| {
+            TransformerType::Template(TemplateOptions {
+                template: t.to_string(),
+            })
+        };
+
+        assert_eq!(template("{{lower first}}.{{last}}").validate(), Ok(()));
+        assert!(template("{{first").validate().is_err());
+        assert!(template("{{#if first}}x").validate().is_err());
+    }
+
+    #[test]
+    fn test_unique_config() {
+        let parse = |s: &str| serde_yaml::from_str::<Transformation>(s).unwrap().unique;
+
+        let enabled = parse("{column: email, transformer: email, unique: true}");
+        assert_eq!(enabled.unwrap().options(), Some(UniqueOptions::default()));
+
+        let options = parse(indoc::indoc! {r#"
+            column: email
+            transformer: email
+            unique:
+              max_attempts: 5
+              max_in_memory: 1000
+        "#});
+        assert_eq!(
+            options.unwrap().options(),
+            Some(UniqueOptions {
+                max_attempts: Some(5),
+                max_in_memory: Some(1000),
+            })
+        );
+
+        let config = |transformer: &str| {
+            Config::new_from_str(&format!(
+                indoc::indoc! {r#"
+                    source:
+                      connection_uri: postgres://localhost
+                      tables:
+                        - name: patients
+                          transform:
+                            - column: mrn
+                              transformer: {}
+                              unique: true
+                    store:
+                      bucket: bucket
+                      aws_access_key_id: key
+                      aws_secret_access_key: secret
+                    destination:
+                      connection_uri: postgres://localhost
+                "#},
+                transformer
+            ))
+        };
+        assert!(config("uuid").validate().is_ok());
+        assert!(config("reverse").validate().is_err());
+    }
+
+    #[test]
+    fn test_locale_config() {
+        let config = Config::new_from_str(indoc::indoc! {r#"
+            locale: fr_FR
+            source:
+              connection_uri: postgres://localhost
+              tables:
+                - name: patients
+                  transform:
+                    - column: first_name
+                      transformer: first-name
+                    - column: city
+                      transformer: city
+                      properties:
+                        locale: pt_BR
+            store:
+              bucket: bucket
+              aws_access_key_id: key
+              aws_secret_access_key: secret
+            destination:
+              connection_uri: postgres://localhost
+        "#});
+
+        let transformers: Vec<_> = config.source.tables[0]
+            .transform
+            .iter()
+            .flatten()
+            .map(|t| t.transformer.clone())
+            .collect();
+        assert_eq!(
+            transformers,
+            vec![
+                TransformerType::FirstName(Some(LocaleOptions {
+                    locale: Some(Locale::FrFr)
+                })),
+                TransformerType::City(Some(LocaleOptions {
+                    locale: Some(Locale::PtBr)
+                })),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_config() {
+        let config = Config::new_from_str(indoc::indoc! {r#"
+            locale: fr_FR
+            source:
+              connection_uri: postgres://localhost
+              tables:
+                - name: patients
+                  transform:
+                    - column: metadata
+                      transformer: json
+                      properties:
+                        paths:
+                          - path: $.contact.name
+                            transformer: first-name
+                          - path: $.visits[*].notes
+                            transformer: static
+                            properties:
+                              value: redacted
+            store:
+              bucket: bucket
+              aws_access_key_id: key
+              aws_secret_access_key: secret
+            destination:
+              connection_uri: postgres://localhost
+        "#});
+
+        let transformation = config.transformation("patients", "metadata").unwrap();
+        assert_eq!(
+            transformation.transformer,
+            TransformerType::Json(PathOptions {
+                paths: vec![
+                    PathRule {
+                        path: "$.contact.name".to_string(),
+                        transformer: TransformerType::FirstName(Some(LocaleOptions {
+                            locale: Some(Locale::FrFr)
+                        })),
+                    },
+                    PathRule {
+                        path: "$.visits[*].notes".to_string(),
+                        transformer: TransformerType::Static(StaticOptions {
+                            value: "redacted".to_string()
+                        }),
+                    },
+                ]
+            })
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_each_config() {
+        let transformer: TransformerType = serde_yaml::from_str(indoc::indoc! {r#"
+            transformer: each
+            properties:
+              delimiter: ","
+              transformer: static
+              properties:
+                value: redacted
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            transformer,
+            TransformerType::Each(EachOptions {
+                delimiter: Some(",".to_string()),
+                transformer: Box::new(TransformerType::Static(StaticOptions {
+                    value: "redacted".to_string()
+                })),
+            })
+        );
+    }
+
+    #[test]
+    fn test_includes_and_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.yml"),
+            indoc::indoc! {r#"
+                profiles:
+                  patient_pii:
+                    - column: first_name
+                      transformer: first-name
+                    - column: ssn
+                      transformer: ssn
+                source:
+                  connection_uri: postgres://base
+                  tables:
+                    - name: patients
+                      transform_profile: patient_pii
+                      transform:
+                        - column: ssn
+                          transformer: "null"
+                store:
+                  bucket: base
+                  aws_access_key_id: key
+                  aws_secret_access_key: secret
+                destination:
+                  connection_uri: postgres://base
+            "#},
+        )
+        .unwrap();
+        let file = dir.path().join("staging.yml");
+        fs::write(
+            &file,
+            indoc::indoc! {r#"
+                include:
+                  - base.yml
+                defaults: &defaults
+                  connection_uri: postgres://staging
+                destination:
+                  <<: *defaults
+            "#},
+        )
+        .unwrap();
+
+        let config = Config::new(file.to_str().unwrap()).unwrap();
+        assert_eq!(config.source.connection_uri.expose(), "postgres://base");
+        assert_eq!(
+            config.destination.connection_uri.expose(),
+            "postgres://staging"
+        );
+        assert_eq!(config.store.bucket, "base");
+
+        let columns: Vec<_> = config.source.tables[0]
+            .transform
+            .iter()
+            .flatten()
+            .map(|t| (t.column.as_str(), t.transformer.clone()))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("first_name", TransformerType::FirstName(None)),
+                ("ssn", TransformerType::Null),
+            ]
+        );
+
+        fs::write(dir.path().join("base.yml"), "include: [staging.yml]").unwrap();
+        assert!(Config::new(file.to_str().unwrap())
+            .unwrap_err()
+            .ends_with("includes itself"));
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema: serde_json::Value = serde_json::from_str(&schema()).unwrap();
+        let variants = schema["definitions"]["Transformation"]["oneOf"]
+            .as_array()
+            .unwrap();
+
+        let ids: Vec<&str> = variants
+            .iter()
+            .map(|v| v["properties"]["transformer"]["enum"][0].as_str().unwrap())
+            .collect();
+        for (id, _) in crate::transformer::transformers_info() {
+            assert!(ids.contains(&id.as_str()), "{} missing from the schema", id);
+        }
+
+        let first_name = variants
+            .iter()
+            .find(|v| v["properties"]["transformer"]["enum"][0] == "first-name")
+            .unwrap();
+        assert_eq!(first_name["required"], serde_json::json!(["transformer"]));
+    }
+
+    #[test]
+    fn test_excluded_and_renamed_columns() {
+        let table: Table = serde_yaml::from_str(indoc::indoc! {r#"
+            name: clinic.patients
+            destination_table: patients
+            exclude_columns: [raw_payload, ssn_encrypted]
+            rename_columns:
+              dob: date_of_birth
+        "#})
+        .unwrap();
+
+        let columns = ["id", "dob", "raw_payload", "ssn_encrypted", "name"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let columns = table.without_excluded(columns);
+        assert_eq!(columns, vec!["id", "dob", "name"]);
+
+        let destination: Vec<_> = columns
+            .iter()
+            .map(|c| table.destination_column(c))
+            .collect();
+        assert_eq!(destination, vec!["id", "date_of_birth", "name"]);
+        assert_eq!(table.destination_table(), "patients");
     }
 }
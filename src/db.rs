@@ -1,6 +1,44 @@
-use std::env;
+use crate::config::{Store, Table};
+use regex::Regex;
+use std::error::Error as StdError;
 use tokio_postgres::{Client, Error, NoTls};
 
+/// Masks the AWS credentials embedded in a statement
+pub fn redact_sql(sql: &str) -> String {
+    let re = Regex::new(
+        r"(?i)\b(aws_access_key_id|aws_secret_access_key|token|access_key_id|secret_access_key|session_token)(\s*=\s*|\s+')[^;'\s]+",
+    )
+    .unwrap();
+    re.replace_all(sql, "${1}${2}*****").to_string()
+}
+
+/// Quotes an identifier, doubling any quote it contains
+pub fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Splits a `schema.table` name, the schema being optional
+pub fn split_table(name: &str) -> (Option<&str>, &str) {
+    match name.split_once('.') {
+        Some((schema, table)) => (Some(schema), table),
+        None => (None, name),
+    }
+}
+
+/// Quotes a table name, qualified with its schema or not
+pub fn quote_table(name: &str) -> String {
+    match split_table(name) {
+        (Some(schema), table) => format!("{}.{}", quote_ident(schema), quote_ident(table)),
+        (None, table) => quote_ident(table),
+    }
+}
+
+pub fn log_sql(label: &str, sql: &str) {
+    if log::log_enabled!(log::Level::Debug) {
+        log::debug!("SQL[{}] = {}", label, redact_sql(sql));
+    }
+}
+
 pub struct Db {
     pub uri: String,
     pub client: Client,
@@ -38,7 +76,7 @@ impl Db {
     pub async fn count(&self, table: &str) -> Result<i64, Error> {
         let count = self
             .client
-            .query_one(&format!("SELECT COUNT(*) FROM {}", table), &[])
+            .query_one(&format!("SELECT COUNT(*) FROM {}", quote_table(table)), &[])
             .await?;
         Ok(count.get(0))
     }
@@ -47,58 +85,139 @@ impl Db {
         self.client.query(sql, &[]).await
     }
 
-    pub async fn columns(&self, table: &str) -> Result<Vec<String>, Error> {
-        let columns = self
-            .client
-            .query(
-                "SELECT column_name FROM information_schema.columns WHERE table_name = $1",
-                &[&table],
-            )
-            .await?;
+    /// Columns of the table in their declared order. Tables without a schema
+    /// are looked up in the current schema.
+    pub async fn columns(&self, name: &str) -> Result<Vec<String>, Box<dyn StdError>> {
+        let columns = match split_table(name) {
+            (Some(schema), table) => {
+                self.client
+                    .query(
+                        "SELECT column_name::text FROM information_schema.columns
+                         WHERE table_schema = $1 AND table_name = $2
+                         ORDER BY ordinal_position",
+                        &[&schema, &table],
+                    )
+                    .await?
+            }
+            (None, table) => {
+                self.client
+                    .query(
+                        "SELECT column_name::text FROM information_schema.columns
+                         WHERE table_schema = current_schema() AND table_name = $1
+                         ORDER BY ordinal_position",
+                        &[&table],
+                    )
+                    .await?
+            }
+        };
+        if columns.is_empty() {
+            return Err(format!("no columns found for table {}, does it exist?", name).into());
+        }
         Ok(columns.iter().map(|row| row.get(0)).collect())
     }
 
+    /// Columns extracted from the table: its `columns`, or all of its
+    /// columns but the excluded ones
+    pub async fn table_columns(&self, table: &Table) -> Result<Vec<String>, Box<dyn StdError>> {
+        let columns = match &table.columns {
+            Some(columns) => columns.clone(),
+            None => self.columns(&table.name).await?,
+        };
+        let columns = table.without_excluded(columns);
+        if columns.is_empty() {
+            return Err(format!("no columns to extract from table {}", table.name).into());
+        }
+        Ok(columns)
+    }
+
     pub async fn unload_table(
         &self,
-        table: &str,
-        limit: &Option<usize>,
-        to_bucket: &str,
-    ) -> Result<u64, Error> {
-        let columns = self.columns(table).await?;
-        let sql = if let Some(limit) = limit {
+        table: &Table,
+        store: &Store,
+    ) -> Result<u64, Box<dyn StdError>> {
+        let columns: Vec<String> = self
+            .table_columns(table)
+            .await?
+            .iter()
+            .map(|c| quote_ident(c))
+            .collect();
+        let name = &table.name;
+        let sql = if let Some(limit) = table.limit {
             format!(
                 "SELECT * FROM (SELECT {} FROM {} LIMIT {})",
                 columns.join(", "),
-                table,
+                quote_table(name),
                 limit
             )
         } else {
-            format!("SELECT {} FROM {}", columns.join(", "), table)
+            format!("SELECT {} FROM {}", columns.join(", "), quote_table(name))
         };
-        log::debug!("SQL[{}] = {}", table, sql);
-        self.unload(&sql, to_bucket, table).await
+        // Quoted identifiers may contain quotes, which `unload` expects
+        // escaped for its string literal, as in `from` queries
+        Ok(self.unload(&sql.replace('\'', "''"), store, name).await?)
     }
 
-    pub async fn unload(&self, sql: &str, to_bucket: &str, to_file: &str) -> Result<u64, Error> {
+    pub async fn unload(&self, sql: &str, store: &Store, to_file: &str) -> Result<u64, Error> {
         let sql = &format!(
             r#"
                 UNLOAD ('{}') TO 's3://{}/in/{}_'
-                CREDENTIALS 'aws_access_key_id={};aws_secret_access_key={}'
+                {}
                 CSV HEADER ALLOWOVERWRITE PARALLEL OFF;
             "#,
             sql,
-            to_bucket,
+            store.bucket,
             to_file,
-            // FIXME replace with taking a config as param
-            env::var("AWS_ACCESS_KEY_ID").unwrap(),
-            env::var("AWS_SECRET_ACCESS_KEY").unwrap()
+            store.authorization().unwrap()
         );
 
-        log::debug!("SQL = {}", sql);
-        Ok(self.client.execute(sql, &[]).await?)
+        log_sql(to_file, sql);
+        self.client.execute(sql, &[]).await
     }
 
     pub async fn exec(&self, sql: &str) -> Result<u64, Error> {
         self.client.execute(sql, &[]).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_sql() {
+        let store = Store {
+            bucket: "bucket".to_string(),
+            aws_access_key_id: Some("AKIAKEY".into()),
+            aws_secret_access_key: Some("topsecret".into()),
+            iam_role: None,
+        };
+        let sql = format!(
+            "COPY t FROM 's3://bucket/out/t.csv' {}",
+            store.authorization().unwrap()
+        );
+        assert_eq!(
+            redact_sql(&sql),
+            "COPY t FROM 's3://bucket/out/t.csv' CREDENTIALS 'aws_access_key_id=*****;aws_secret_access_key=*****'"
+        );
+        assert_eq!(
+            redact_sql("UNLOAD ('SELECT 1') TO 's3://b/in/t_' ACCESS_KEY_ID 'AKIAKEY' SECRET_ACCESS_KEY 'topsecret'"),
+            "UNLOAD ('SELECT 1') TO 's3://b/in/t_' ACCESS_KEY_ID '*****' SECRET_ACCESS_KEY '*****'"
+        );
+
+        let store = Store {
+            iam_role: Some("arn:aws:iam::123456789012:role/redshift".to_string()),
+            ..store
+        };
+        assert_eq!(
+            store.authorization().unwrap(),
+            "IAM_ROLE 'arn:aws:iam::123456789012:role/redshift'"
+        );
+    }
+
+    #[test]
+    fn test_quote_table() {
+        assert_eq!(quote_table("patients"), "\"patients\"");
+        assert_eq!(quote_table("clinic.patients"), "\"clinic\".\"patients\"");
+        assert_eq!(quote_ident("odd\"name"), "\"odd\"\"name\"");
+    }
+}
@@ -0,0 +1,268 @@
+use crate::db::Db;
+use tokio_postgres::Error;
+
+pub struct TableInfo {
+    pub name: String,
+    pub columns: Vec<(String, String)>,
+}
+
+/// Proposes a transformer for a column from its name and type, as the YAML
+/// lines of the transformation after `column:`
+pub fn suggest_transformer(column: &str, data_type: &str) -> Option<&'static str> {
+    let name = column.to_lowercase();
+    let is = |names: &[&str]| {
+        names
+            .iter()
+            .any(|n| name == *n || name.ends_with(&format!("_{}", n)))
+    };
+    let has = |parts: &[&str]| parts.iter().any(|p| name.contains(p));
+    let text = matches!(data_type, "text" | "character varying" | "character");
+
+    let suggestion = if is(&["dob", "birth_date", "birthdate", "date_of_birth"]) {
+        "transformer: date\n  properties:\n    format: '%Y-%m-%d'"
+    } else if is(&["ip", "ip_address"]) {
+        "transformer: ipv4"
+    } else if !text {
+        // the remaining transformers generate strings, which a boolean or
+        // timestamp column like `email_verified` wouldn't accept
+        return None;
+    } else if has(&["email"]) {
+        "transformer: email"
+    } else if is(&["first_name", "firstname", "given_name", "fname"]) {
+        "transformer: first-name"
+    } else if is(&["last_name", "lastname", "surname", "family_name", "lname"]) {
+        "transformer: last-name"
+    } else if is(&["full_name", "fullname", "name"]) {
+        "transformer: full-name"
+    } else if is(&["ssn", "social_security_number"]) {
+        "transformer: ssn"
+    } else if is(&["npi"]) {
+        "transformer: npi"
+    } else if has(&["phone", "mobile", "fax"]) {
+        "transformer: phone"
+    } else if has(&["credit_card", "card_number", "cc_number"]) {
+        "transformer: credit-card"
+    } else if is(&[
+        "address2",
+        "address_2",
+        "address_line2",
+        "address_line_2",
+        "apartment",
+        "suite",
+    ]) {
+        "transformer: secondary-address"
+    } else if is(&[
+        "street",
+        "address",
+        "address1",
+        "address_1",
+        "address_line1",
+        "address_line_1",
+    ]) {
+        "transformer: street"
+    } else if is(&["city"]) {
+        "transformer: city"
+    } else if is(&["state"]) {
+        "transformer: state"
+    } else if has(&["zip", "postal_code", "postcode"]) {
+        "transformer: zip-code"
+    } else if is(&["username", "user_name", "login"]) {
+        "transformer: username"
+    } else if is(&["company", "company_name", "employer", "organization"]) {
+        "transformer: company-name"
+    } else if is(&["job_title", "occupation"]) {
+        "transformer: job-title"
+    } else if is(&["notes", "note", "comments", "comment", "memo"]) {
+        "transformer: redact\n  properties: {}"
+    } else {
+        return None;
+    };
+    Some(suggestion)
+}
+
+/// Lists the tables of a schema with their columns and types. Tables outside
+/// of the current schema are qualified with their schema, as unqualified
+/// names are looked up in the current schema.
+pub async fn introspect(uri: &str, schema: &str) -> Result<Vec<TableInfo>, Error> {
+    let db = Db::new(uri).await;
+    let current: Option<String> = db
+        .client
+        .query_one("SELECT current_schema()::text", &[])
+        .await?
+        .get(0);
+    let rows = db
+        .client
+        .query(
+            "SELECT table_name::text, column_name::text, data_type::text
+             FROM information_schema.columns
+             WHERE table_schema = $1
+             ORDER BY table_name, ordinal_position",
+            &[&schema],
+        )
+        .await?;
+
+    let mut tables: Vec<TableInfo> = vec![];
+    for row in rows {
+        let table: String = match &current {
+            Some(current) if current == schema => row.get(0),
+            _ => format!("{}.{}", schema, row.get::<_, String>(0)),
+        };
+        let column = (row.get(1), row.get(2));
+        match tables.last_mut() {
+            Some(last) if last.name == table => last.columns.push(column),
+            _ => tables.push(TableInfo {
+                name: table,
+                columns: vec![column],
+            }),
+        }
+    }
+    Ok(tables)
+}
+
+/// Renders a starter config for the tables, with variables for the
+/// connections and credentials
+pub fn render(tables: &[TableInfo]) -> String {
+    let mut yaml = String::from(indoc::indoc! {"
+        # Starter configuration generated by joindoe init. The transformers were
+        # proposed from the column names, review them before running it.
+        source:
+          connection_uri: ${SOURCE_DATABASE_URL}
+          tables:
+    "});
+
+    for table in tables {
+        yaml.push_str(&format!("    - name: {}\n", scalar(&table.name)));
+
+        let mut untouched = vec![];
+        let mut transform = vec![];
+        for (column, data_type) in &table.columns {
+            match suggest_transformer(column, data_type) {
+                Some(suggestion) => transform.push(format!(
+                    "        - column: {}\n          {}\n",
+                    scalar(column),
+                    suggestion.replace('\n', "\n        ")
+                )),
+                None => untouched.push(scalar(column)),
+            }
+        }
+
+        if !untouched.is_empty() {
+            yaml.push_str(&format!("      # Copied as is: {}\n", untouched.join(", ")));
+        }
+        if !transform.is_empty() {
+            yaml.push_str("      transform:\n");
+            yaml.push_str(&transform.concat());
+        }
+    }
+
+    yaml.push_str(indoc::indoc! {"
+        store:
+          bucket: ${JOINDOE_BUCKET}
+          # Or set iam_role to the ARN of a role Redshift can assume
+          aws_access_key_id: ${AWS_ACCESS_KEY_ID}
+          aws_secret_access_key: ${AWS_SECRET_ACCESS_KEY}
+        destination:
+          connection_uri: ${TARGET_DATABASE_URL}
+    "});
+    yaml
+}
+
+/// The value as a YAML scalar, quoted when it would otherwise be read as
+/// something else, and with `$` escaped from the interpolation
+fn scalar(value: &str) -> String {
+    let yaml = serde_yaml::to_string(&value.replace('$', "$$")).unwrap();
+    yaml.trim_start_matches("---\n").trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, TransformerType};
+
+    #[test]
+    fn test_render() {
+        let tables = vec![
+            TableInfo {
+                name: "patients".to_string(),
+                columns: vec![
+                    ("id".to_string(), "integer".to_string()),
+                    ("first_name".to_string(), "character varying".to_string()),
+                    (
+                        "patient_last_name".to_string(),
+                        "character varying".to_string(),
+                    ),
+                    ("date_of_birth".to_string(), "date".to_string()),
+                    ("home_phone".to_string(), "character varying".to_string()),
+                    ("email_verified".to_string(), "boolean".to_string()),
+                    (
+                        "phone_confirmed_at".to_string(),
+                        "timestamp without time zone".to_string(),
+                    ),
+                    ("notes".to_string(), "text".to_string()),
+                    ("status".to_string(), "character varying".to_string()),
+                ],
+            },
+            TableInfo {
+                name: "orders".to_string(),
+                columns: vec![("id".to_string(), "integer".to_string())],
+            },
+            TableInfo {
+                name: "null".to_string(),
+                columns: vec![
+                    ("on".to_string(), "integer".to_string()),
+                    ("email: #1".to_string(), "text".to_string()),
+                    ("*ref".to_string(), "text".to_string()),
+                    ("$email".to_string(), "text".to_string()),
+                ],
+            },
+        ];
+
+        let config = Config::new_from_str(&render(&tables));
+        let transform: Vec<_> = config.source.tables[0]
+            .transform
+            .iter()
+            .flatten()
+            .map(|t| t.column.as_str())
+            .collect();
+        assert_eq!(
+            transform,
+            vec![
+                "first_name",
+                "patient_last_name",
+                "date_of_birth",
+                "home_phone",
+                "notes"
+            ]
+        );
+        assert_eq!(
+            config
+                .transformation("patients", "home_phone")
+                .unwrap()
+                .transformer,
+            TransformerType::Phone(None)
+        );
+        assert_eq!(config.source.tables[1].transform, None);
+
+        for var in [
+            "SOURCE_DATABASE_URL",
+            "JOINDOE_BUCKET",
+            "AWS_ACCESS_KEY_ID",
+            "AWS_SECRET_ACCESS_KEY",
+            "TARGET_DATABASE_URL",
+        ] {
+            std::env::set_var(var, "value");
+        }
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), render(&tables[2..])).unwrap();
+        let config = Config::new(file.path().to_str().unwrap()).unwrap();
+        let table = &config.source.tables[0];
+        assert_eq!(table.name, "null");
+        let columns: Vec<_> = table
+            .transform
+            .iter()
+            .flatten()
+            .map(|t| t.column.as_str())
+            .collect();
+        assert_eq!(columns, vec!["email: #1", "$email"]);
+    }
+}
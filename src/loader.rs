@@ -1,12 +1,12 @@
 use crate::config::Config;
-use crate::db::Db;
+use crate::db::{self, Db};
+use std::error::Error;
 use std::time::Instant;
-use tokio_postgres::Error;
 
-pub async fn load(config: &Config) -> Result<(), Error> {
+pub async fn load(config: &Config) -> Result<(), Box<dyn Error>> {
     let src_def = &config.source;
-    let source = Db::new(&src_def.connection_uri).await;
-    let destination = Db::new(&config.destination.connection_uri).await;
+    let source = Db::new(src_def.connection_uri.expose()).await;
+    let destination = Db::new(config.destination.connection_uri.expose()).await;
 
     log::debug!(
         "Connected to destination: postgres://*****@{}",
@@ -14,32 +14,33 @@ pub async fn load(config: &Config) -> Result<(), Error> {
     );
 
     for table in &src_def.tables {
-        log::info!("Loading table {}...", table.name);
+        let destination_table = db::quote_table(table.destination_table());
+        log::info!("Loading table {} into {}...", table.name, destination_table);
         let now = Instant::now();
-        let columns = if let Some(columns) = &table.columns {
-            columns.clone()
-        } else {
-            source.columns(&table.name).await.unwrap()
-        };
+        let columns: Vec<String> = source
+            .table_columns(table)
+            .await?
+            .iter()
+            .map(|c| db::quote_ident(table.destination_column(c)))
+            .collect();
 
         let sql = format!(
             r#"
                 COPY {}({}) FROM 's3://{}/out/{}.csv'
-                CREDENTIALS 'aws_access_key_id={};aws_secret_access_key={}'
+                {}
                 CSV BLANKSASNULL EXPLICIT_IDS;
             "#,
-            table.name,
+            destination_table,
             columns.join(", "),
             config.store.bucket,
             table.name,
-            config.store.aws_access_key_id,
-            config.store.aws_secret_access_key,
+            config.store.authorization().unwrap(),
         );
 
-        log::debug!("SQL[{}] = {}", table.name, sql);
+        db::log_sql(&table.name, &sql);
 
         destination
-            .exec(format!("TRUNCATE TABLE {}", table.name).as_str())
+            .exec(format!("TRUNCATE TABLE {}", destination_table).as_str())
             .await?;
         destination.exec(&sql).await?;
 
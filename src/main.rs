@@ -4,10 +4,12 @@ use colored::Colorize;
 mod collect;
 mod config;
 mod db;
+mod init;
 mod loader;
 mod postprocess;
 mod transform;
 mod transformer;
+mod unique;
 
 #[derive(ValueEnum, Clone, Debug, PartialEq)]
 pub enum Stage {
@@ -21,6 +23,41 @@ pub enum Stage {
 enum Commands {
     /// List all available transformers
     Transformers {},
+    /// Decrypt values encrypted by a fpe transformer
+    Decrypt {
+        /// Table holding the encrypted column
+        #[clap(long)]
+        table: String,
+        /// Column encrypted with the fpe transformer
+        #[clap(long)]
+        column: String,
+        /// Encrypted values
+        #[clap(required = true)]
+        values: Vec<String>,
+    },
+    /// Write a starter configuration from the tables of a database
+    Init {
+        /// Connection URI of the source database
+        #[clap(long)]
+        source: String,
+        /// Schema whose tables are included
+        #[clap(long, default_value = "public")]
+        schema: String,
+        /// File the configuration is written to, instead of the standard output
+        #[clap(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Configuration file utilities
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    /// Print the JSON Schema of the configuration file
+    Schema {},
 }
 
 #[derive(Parser, Debug)]
@@ -81,11 +118,43 @@ async fn main() {
             }
             return;
         }
+        Some(Commands::Decrypt {
+            table,
+            column,
+            values,
+        }) => {
+            decrypt(&args.config, table, column, values);
+            return;
+        }
+        Some(Commands::Init {
+            source,
+            schema,
+            output,
+        }) => {
+            init(source, schema, output).await;
+            return;
+        }
+        Some(Commands::Config {
+            command: ConfigCommands::Schema {},
+        }) => {
+            println!("{}", config::schema());
+            return;
+        }
         None => {}
     }
 
     let config = args.config.unwrap();
-    let config = config::Config::new(&config);
+    let config = match config::Config::new(&config) {
+        Ok(config) => config,
+        Err(e) => {
+            log::error!("Invalid configuration: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = config.validate() {
+        log::error!("Invalid configuration: {}", e);
+        std::process::exit(1);
+    }
     if !args.skip_collect && allow_run(&args.only, Stage::Collect) {
         match collect::collect(&config).await {
             Ok(_) => log::info!("Collection phase completed"),
@@ -124,6 +193,73 @@ async fn main() {
     }
 }
 
+async fn init(source: &str, schema: &str, output: &Option<String>) {
+    let tables = match init::introspect(source, schema).await {
+        Ok(tables) => tables,
+        Err(e) => {
+            log::error!("Could not read the tables of {}: {}", schema, e);
+            std::process::exit(1);
+        }
+    };
+    let yaml = init::render(&tables);
+
+    match output {
+        Some(file) => {
+            std::fs::write(file, yaml).unwrap();
+            log::info!("Wrote {} tables to {}", tables.len(), file);
+        }
+        None => print!("{}", yaml),
+    }
+}
+
+fn decrypt(config: &Option<String>, table: &str, column: &str, values: &[String]) {
+    let config = match config {
+        Some(config) => match config::Config::new(config) {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Invalid configuration: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => {
+            log::error!("A configuration file is required to decrypt values");
+            std::process::exit(1);
+        }
+    };
+
+    let options = config
+        .transformation(table, column)
+        .into_iter()
+        .flat_map(|t| std::iter::once(&t.transformer).chain(&t.otherwise))
+        .find_map(|t| match t {
+            config::TransformerType::Fpe(options) => Some(options),
+            _ => None,
+        });
+    let options = match options {
+        Some(options) => options,
+        None => {
+            log::error!("No fpe transformer configured for {}.{}", table, column);
+            std::process::exit(1);
+        }
+    };
+
+    let transformer =
+        transformer::FpeTransformer::new(options.key.expose(), &options.alphabet, &options.tweak)
+            .unwrap_or_else(|e| {
+                log::error!("Invalid fpe configuration: {}", e);
+                std::process::exit(1);
+            });
+    for value in values {
+        match transformer.decrypt(value) {
+            Ok(decrypted) => println!("{}", decrypted),
+            Err(e) => {
+                log::error!("Decryption failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
 fn allow_run(only: &Option<Vec<Stage>>, stage: Stage) -> bool {
     if let Some(v) = only {
         v.contains(&stage)
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::config::{Config, TaskType};
-use crate::db::Db;
+use crate::db::{self, Db};
 use handlebars::Handlebars;
 use printpdf::*;
 use s3::bucket::Bucket;
@@ -19,15 +19,15 @@ pub async fn run(config: &Config) -> Result<(), Error> {
 
             match &task_def.task {
                 TaskType::Sql(sql_config) => {
-                    let db = Db::new(&sql_config.connection_uri).await;
-                    log::debug!("Running SQL: {}", sql_config.sql);
+                    let db = Db::new(sql_config.connection_uri.expose()).await;
+                    db::log_sql(&task_def.name, &sql_config.sql);
                     let results = db.exec(&sql_config.sql).await.unwrap();
                     log::debug!("{} affected records", results);
                 }
                 TaskType::Pdf(pdf_config) => {
                     let credentials = Credentials::new(
-                        Some(&pdf_config.aws_access_key_id.clone()),
-                        Some(&pdf_config.aws_secret_access_key.clone()),
+                        Some(pdf_config.aws_access_key_id.expose()),
+                        Some(pdf_config.aws_secret_access_key.expose()),
                         None,
                         None,
                         None,
@@ -39,12 +39,11 @@ pub async fn run(config: &Config) -> Result<(), Error> {
                     let results = bucket.list("".to_string(), None).await.unwrap();
                     let contents = results
                         .iter()
-                        .map(|r| r.contents.clone())
-                        .flatten()
+                        .flat_map(|r| r.contents.clone())
                         .collect::<Vec<_>>();
                     let existing_pdfs = contents.iter().map(|o| o.key.clone()).collect::<Vec<_>>();
 
-                    let client = Db::new(&config.destination.connection_uri).await;
+                    let client = Db::new(config.destination.connection_uri.expose()).await;
                     let mut handlebars = Handlebars::new();
                     handlebars
                         .register_template_string("file_name", &pdf_config.file_name)
@@ -86,7 +85,7 @@ pub async fn run(config: &Config) -> Result<(), Error> {
 
                         let file = NamedTempFile::new().unwrap();
                         let path = file.path();
-                        doc.save(&mut BufWriter::new(File::create(&path).unwrap()))
+                        doc.save(&mut BufWriter::new(File::create(path).unwrap()))
                             .unwrap();
 
                         log::debug!("Uploading PDF {}/{} - {}...", i, count, file_name);
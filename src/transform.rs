@@ -1,20 +1,21 @@
-use crate::config::{Config, Transformation};
+use crate::config::{Config, RandomValueOptions, RowCondition, Transformation};
 use crate::db;
-use crate::transformer::TransformationContext;
+use crate::transformer::{RandomValueTransformer, TransformationContext, Transformer};
+use crate::unique::UniqueValues;
 use s3::creds::Credentials;
 use s3::{bucket::Bucket, serde_types::Object};
+use std::error::Error;
 use std::io::BufReader;
 use std::time::Instant;
 use tempfile::NamedTempFile;
-use tokio_postgres::Error;
 
-pub async fn transform(config: &Config) -> Result<(), Error> {
+pub async fn transform(config: &Config) -> Result<(), Box<dyn Error>> {
     let store = &config.store;
     let tables = &config.source.tables;
 
     let credentials = Credentials::new(
-        Some(&store.aws_access_key_id.clone()),
-        Some(&store.aws_secret_access_key.clone()),
+        store.aws_access_key_id.as_ref().map(|k| k.expose()),
+        store.aws_secret_access_key.as_ref().map(|k| k.expose()),
         None,
         None,
         None,
@@ -33,14 +34,14 @@ pub async fn transform(config: &Config) -> Result<(), Error> {
             transform = v;
         }
 
-        let columns = if let Some(columns) = &table_obj.columns {
-            columns.clone()
-        } else {
-            db::Db::new(&config.source.connection_uri)
-                .await
-                .columns(&table)
-                .await?
-        };
+        let columns = db::Db::new(config.source.connection_uri.expose())
+            .await
+            .table_columns(table_obj)
+            .await?;
+        let transform =
+            sample_distributions(config.source.connection_uri.expose(), transform).await?;
+        let mut row_transformer = RowTransformer::new(&transform, columns)
+            .map_err(|e| format!("table {}: {}", table, e))?;
         let now = Instant::now();
 
         let results = bucket
@@ -48,7 +49,7 @@ pub async fn transform(config: &Config) -> Result<(), Error> {
             .await
             .unwrap();
 
-        if results.len() < 1 {
+        if results.is_empty() {
             log::info!("No records to process, exiting");
             return Ok(());
         }
@@ -74,7 +75,9 @@ pub async fn transform(config: &Config) -> Result<(), Error> {
             for (i, result) in reader.records().enumerate() {
                 let record = result.unwrap();
                 let data = record.iter().collect::<Vec<&str>>();
-                let res = apply_transformations(i, &transform, data, columns.clone());
+                let res = row_transformer
+                    .apply(i, data)
+                    .map_err(|e| format!("table {}: {}", table, e))?;
                 writer.write_record(res).unwrap();
             }
 
@@ -99,62 +102,405 @@ pub async fn transform(config: &Config) -> Result<(), Error> {
     Ok(())
 }
 
-pub fn apply_transformations(
-    index: usize,
-    transformations: &Vec<Transformation>,
-    data: Vec<&str>,
+/// Replaces the `sample` query of random value transformations, including
+/// the ones nested in other transformers, with the values and weights it
+/// returns from the source database
+pub async fn sample_distributions(
+    uri: &str,
+    transformations: &[Transformation],
+) -> Result<Vec<Transformation>, Box<dyn Error>> {
+    let mut resolved = transformations.to_vec();
+    let sampled: Vec<(&String, &mut RandomValueOptions)> = resolved
+        .iter_mut()
+        .flat_map(|t| {
+            std::iter::once(&mut t.transformer)
+                .chain(t.otherwise.as_mut())
+                .flat_map(|t| t.sampled_mut())
+                .map(|options| (&t.column, options))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    if sampled.is_empty() {
+        return Ok(resolved);
+    }
+
+    let db = db::Db::new(uri).await;
+    for (column, options) in sampled {
+        if let Some(sql) = options.sample.take() {
+            db::log_sql(&format!("sample {}", column), &sql);
+            let mut values = vec![];
+            let mut weights = vec![];
+            for row in db.query(&sql).await? {
+                let (value, count) = row
+                    .try_get::<_, Option<String>>(0)
+                    .and_then(|value| Ok((value, row.try_get::<_, i64>(1)?)))
+                    .map_err(|e| {
+                        format!(
+                            "sample {}: the query must return a text value and a bigint count: {}",
+                            column, e
+                        )
+                    })?;
+                values.push(value.unwrap_or_default());
+                weights.push(count as f64);
+            }
+            options.values = values;
+            options.weights = Some(weights);
+            RandomValueTransformer::new(&options.values, &options.weights)
+                .map_err(|e| format!("sample {}: {}", column, e))?;
+        }
+    }
+
+    Ok(resolved)
+}
+
+struct PreparedTransformation {
+    transformer: Box<dyn Transformer>,
+    when: Option<RowCondition>,
+    otherwise: Option<Box<dyn Transformer>>,
+    unique: Option<(UniqueValues, usize)>,
+}
+
+/// Transformations of a table, built once and applied to each of its rows
+pub struct RowTransformer {
     columns: Vec<String>,
-) -> Vec<String> {
-    let mut trdata = vec![];
+    order: Vec<usize>,
+    transformations: Vec<Option<PreparedTransformation>>,
+}
+
+impl RowTransformer {
+    pub fn new(transformations: &[Transformation], columns: Vec<String>) -> Result<Self, String> {
+        let order = evaluation_order(transformations, &columns)?;
+        let transformations = columns
+            .iter()
+            .map(|column| {
+                transformations
+                    .iter()
+                    .find(|t| t.column == *column)
+                    .map(|t| {
+                        let dependencies = std::iter::once(&t.transformer)
+                            .chain(&t.otherwise)
+                            .flat_map(|t| t.dependencies());
+                        for dependency in dependencies {
+                            if !columns.contains(&dependency) {
+                                return Err(format!(
+                                    "{}: column {} is not extracted",
+                                    column, dependency
+                                ));
+                            }
+                        }
+
+                        Ok(PreparedTransformation {
+                            transformer: t.transformer.transformer(),
+                            when: t
+                                .when
+                                .as_ref()
+                                .map(|c| c.compile(&columns))
+                                .transpose()
+                                .map_err(|e| format!("{}: {}", column, e))?,
+                            otherwise: t.otherwise.as_ref().map(|o| o.transformer()),
+                            unique: t.unique.as_ref().and_then(|u| u.options()).map(|o| {
+                                (
+                                    UniqueValues::new(o.max_in_memory),
+                                    o.max_attempts.unwrap_or(100),
+                                )
+                            }),
+                        })
+                    })
+                    .transpose()
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(RowTransformer {
+            columns,
+            order,
+            transformations,
+        })
+    }
 
-    for (i, column) in columns.iter().enumerate() {
-        let value = data[i];
+    pub fn apply(&mut self, index: usize, data: Vec<&str>) -> Result<Vec<String>, String> {
+        let mut trdata: Vec<String> = data.iter().map(|v| v.to_string()).collect();
 
-        let transformation = transformations.iter().find(|t| t.column == *column);
-        if transformation.is_none() {
-            trdata.push(value.to_string());
-            continue;
+        for &i in &self.order {
+            let transformation = match &mut self.transformations[i] {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let ctx = TransformationContext {
+                index,
+                row: data.clone(),
+                transformed: &trdata,
+                columns: self.columns.clone(),
+                value: data[i],
+            };
+
+            let transformer = match &transformation.when {
+                Some(condition) if !condition.matches(&data) => match &transformation.otherwise {
+                    Some(otherwise) => otherwise,
+                    None => continue,
+                },
+                _ => &transformation.transformer,
+            };
+            let mut trvalue = transformer.transform(&ctx);
+
+            // Null values don't violate unique constraints, so they aren't tracked
+            if let Some((issued, max_attempts)) = &mut transformation.unique {
+                let mut attempts = 1;
+                while !trvalue.is_empty() && !issued.insert(&trvalue) {
+                    if attempts == *max_attempts {
+                        return Err(format!(
+                            "Could not generate a unique value for column {} after {} attempts, {} values were already issued",
+                            self.columns[i],
+                            max_attempts,
+                            issued.len()
+                        ));
+                    }
+                    trvalue = transformer.transform(&ctx);
+                    attempts += 1;
+                }
+            }
+
+            trdata[i] = trvalue;
         }
 
-        let ctx = TransformationContext {
-            index,
-            row: data.clone(),
-            columns: columns.clone(),
-            value: value,
-        };
+        Ok(trdata)
+    }
+}
+
+/// Returns the column indexes in the order they have to be transformed, so
+/// that columns depending on other transformed values are evaluated last
+pub fn evaluation_order(
+    transformations: &[Transformation],
+    columns: &[String],
+) -> Result<Vec<usize>, String> {
+    let dependencies: Vec<Vec<usize>> = columns
+        .iter()
+        .map(|column| {
+            let transformation = transformations.iter().find(|t| t.column == *column);
+            let mut deps = vec![];
+            if let Some(t) = transformation {
+                deps.extend(t.transformer.dependencies());
+                if let Some(otherwise) = &t.otherwise {
+                    deps.extend(otherwise.dependencies());
+                }
+            }
+            deps.iter()
+                .filter(|d| *d != column && transformations.iter().any(|t| t.column == **d))
+                .filter_map(|d| columns.iter().position(|c| c == d))
+                .collect()
+        })
+        .collect();
 
-        let transformation = transformation.unwrap();
-        let transformer = transformation.transformer.transformer();
-        let trvalue = transformer.transform(&ctx);
+    let mut order: Vec<usize> = vec![];
+    while order.len() < columns.len() {
+        let next = (0..columns.len())
+            .find(|i| !order.contains(i) && dependencies[*i].iter().all(|d| order.contains(d)));
 
-        trdata.push(trvalue.clone());
+        match next {
+            Some(i) => order.push(i),
+            None => {
+                let pending = (0..columns.len())
+                    .filter(|i| !order.contains(i))
+                    .map(|i| columns[i].clone())
+                    .collect::<Vec<_>>();
+                return Err(format!(
+                    "Circular dependency between columns: {}",
+                    pending.join(", ")
+                ));
+            }
+        }
     }
 
-    trdata
+    Ok(order)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::config::TransformerType;
+    use crate::config::{
+        Condition, FromOptions, Predicate, RandomValueOptions, RedactDetector, RedactOptions,
+        RedactReplacement, StaticOptions, TemplateOptions, TransformerType, Unique, ValueSource,
+    };
 
     use super::*;
 
+    fn row_transformer(transformations: &[Transformation], columns: &[&str]) -> RowTransformer {
+        RowTransformer::new(
+            transformations,
+            columns.iter().map(|c| c.to_string()).collect(),
+        )
+        .unwrap()
+    }
+
     #[test]
     fn test_apply_transformations() {
+        let transformations = vec![Transformation::new("identifier", TransformerType::Reverse)];
+        let data = row_transformer(&transformations, &["identifier", "first", "last"])
+            .apply(1, vec!["1184643769", "Martin", "Moore"])
+            .unwrap();
+        assert_eq!(data, vec!["9673464811", "Martin", "Moore"]);
+    }
+
+    #[test]
+    fn test_from_uses_transformed_values() {
+        let from = |column: &str, source: ValueSource| {
+            Transformation::new(
+                column,
+                TransformerType::From(FromOptions {
+                    column: "id".to_string(),
+                    source,
+                }),
+            )
+        };
+        let transformations = vec![
+            from("copy", ValueSource::Transformed),
+            from("original", ValueSource::Original),
+            Transformation::new("id", TransformerType::Sequence),
+        ];
+        let data = row_transformer(&transformations, &["copy", "original", "id"])
+            .apply(1, vec!["", "", "abc"])
+            .unwrap();
+        assert_eq!(data, vec!["2", "abc", "2"]);
+    }
+
+    #[test]
+    fn test_evaluation_order_cycle() {
+        let template = |column: &str, template: &str| {
+            Transformation::new(
+                column,
+                TransformerType::Template(TemplateOptions {
+                    template: template.to_string(),
+                }),
+            )
+        };
+        let transformations = vec![template("a", "{{b}}"), template("b", "{{a}}")];
+        let columns = vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+        assert!(evaluation_order(&transformations, &columns).is_err());
+    }
+
+    #[test]
+    fn test_conditional_transformation() {
+        let transformations = vec![
+            Transformation {
+                when: Some(Condition {
+                    column: "status".to_string(),
+                    predicate: Predicate::Equals("archived".to_string()),
+                }),
+                ..Transformation::new("notes", TransformerType::Null)
+            },
+            Transformation {
+                when: Some(Condition {
+                    column: "email".to_string(),
+                    predicate: Predicate::Matches("@ourcompany\\.com$".to_string()),
+                }),
+                otherwise: Some(TransformerType::Reverse),
+                ..Transformation::new(
+                    "email",
+                    TransformerType::Static(StaticOptions {
+                        value: "test@example.com".to_string(),
+                    }),
+                )
+            },
+        ];
+        let mut transformer = row_transformer(&transformations, &["status", "notes", "email"]);
+
+        let data = transformer
+            .apply(1, vec!["archived", "some notes", "john@ourcompany.com"])
+            .unwrap();
+        assert_eq!(data, vec!["archived", "", "test@example.com"]);
+
+        let data = transformer
+            .apply(2, vec!["active", "some notes", "abc"])
+            .unwrap();
+        assert_eq!(data, vec!["active", "some notes", "cba"]);
+    }
+
+    #[test]
+    fn test_invalid_condition() {
+        let transformation = |column: &str, pattern: &str| {
+            vec![Transformation {
+                when: Some(Condition {
+                    column: column.to_string(),
+                    predicate: Predicate::Matches(pattern.to_string()),
+                }),
+                ..Transformation::new("notes", TransformerType::Null)
+            }]
+        };
+        let columns = vec!["status".to_owned(), "notes".to_owned()];
+
+        assert!(RowTransformer::new(&transformation("status", "^arch"), columns.clone()).is_ok());
+        assert!(RowTransformer::new(&transformation("state", "^arch"), columns.clone()).is_err());
+        assert!(RowTransformer::new(&transformation("status", "(arch"), columns).is_err());
+    }
+
+    #[test]
+    fn test_template_columns_not_extracted() {
+        let transformations = vec![Transformation::new(
+            "name",
+            TransformerType::Template(TemplateOptions {
+                template: "{{first}} {{lower middle}}".to_string(),
+            }),
+        )];
+        let columns = vec!["first".to_owned(), "name".to_owned()];
+
+        assert_eq!(
+            RowTransformer::new(&transformations, columns)
+                .err()
+                .unwrap(),
+            "name: column middle is not extracted"
+        );
+    }
+
+    #[test]
+    fn test_unique_transformation() {
         let transformations = vec![Transformation {
-            column: "identifier".to_string(),
-            transformer: TransformerType::Reverse,
+            unique: Some(Unique::Enabled(true)),
+            ..Transformation::new(
+                "status",
+                TransformerType::RandomValue(RandomValueOptions {
+                    values: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                    weights: None,
+                    sample: None,
+                }),
+            )
         }];
-        let data = apply_transformations(
-            1,
-            &transformations,
-            vec!["1184643769", "Martin", "Moore"],
-            vec![
-                "identifier".to_owned(),
-                "first".to_owned(),
-                "last".to_owned(),
-            ],
-        );
-        assert_eq!(data, vec!["9673464811", "Martin", "Moore"]);
+        let mut transformer = row_transformer(&transformations, &["status"]);
+
+        let mut issued: Vec<String> = (0..3)
+            .map(|i| transformer.apply(i, vec![""]).unwrap().remove(0))
+            .collect();
+        issued.sort();
+        assert_eq!(issued, vec!["a", "b", "c"]);
+
+        let error = transformer.apply(3, vec![""]).unwrap_err();
+        assert!(error.starts_with("Could not generate a unique value for column status"));
+    }
+
+    #[test]
+    fn test_redact_uses_transformed_names() {
+        let transformations = vec![
+            Transformation::new(
+                "notes",
+                TransformerType::Redact(RedactOptions {
+                    detectors: Some(vec![RedactDetector::Ssn]),
+                    patterns: vec![],
+                    name_columns: vec!["name".to_string()],
+                    replace: RedactReplacement::Fake,
+                }),
+            ),
+            Transformation::new(
+                "name",
+                TransformerType::Static(StaticOptions {
+                    value: "Peter".to_string(),
+                }),
+            ),
+        ];
+        let mut transformer = row_transformer(&transformations, &["notes", "name"]);
+
+        let notes = transformer
+            .apply(1, vec!["Called John, ssn 123-45-6789", "John"])
+            .unwrap()
+            .remove(0);
+        assert!(notes.starts_with("Called Peter, ssn "));
+        assert!(!notes.contains("123-45-6789"));
     }
 }
@@ -1,11 +1,16 @@
-use super::{TransformationContext, Transformer};
-use fake::{faker::address::en::CityName, Fake};
+use crate::config::Locale;
 
-pub struct CityTransformer {}
+use super::{localized, TransformationContext, Transformer};
+
+pub struct CityTransformer {
+    locale: Locale,
+}
 
 impl CityTransformer {
-    pub fn default() -> Self {
-        CityTransformer {}
+    pub fn new(locale: &Locale) -> Self {
+        CityTransformer {
+            locale: locale.clone(),
+        }
     }
 }
 
@@ -19,6 +24,6 @@ impl Transformer for CityTransformer {
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
-        CityName().fake()
+        localized!(&self.locale, fake::faker::address::raw::CityName)
     }
 }
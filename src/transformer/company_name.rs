@@ -0,0 +1,29 @@
+use crate::config::Locale;
+
+use super::{localized, TransformationContext, Transformer};
+
+pub struct CompanyNameTransformer {
+    locale: Locale,
+}
+
+impl CompanyNameTransformer {
+    pub fn new(locale: &Locale) -> Self {
+        CompanyNameTransformer {
+            locale: locale.clone(),
+        }
+    }
+}
+
+impl Transformer for CompanyNameTransformer {
+    fn id(&self) -> &str {
+        "company-name"
+    }
+
+    fn description(&self) -> &str {
+        "Random company name"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        localized!(&self.locale, fake::faker::company::raw::CompanyName)
+    }
+}
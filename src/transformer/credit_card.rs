@@ -0,0 +1,68 @@
+use rand::Rng;
+
+use super::{luhn, seeded_rng, TransformationContext, Transformer};
+
+/// Issuer prefixes and card lengths for Visa, Mastercard, Amex and Discover
+const ISSUERS: [(&[u32], usize); 6] = [
+    (&[4], 16),
+    (&[5, 1], 16),
+    (&[5, 5], 16),
+    (&[3, 4], 15),
+    (&[3, 7], 15),
+    (&[6, 0, 1, 1], 16),
+];
+
+pub struct CreditCardTransformer {
+    key: Option<String>,
+}
+
+impl CreditCardTransformer {
+    pub fn new(key: Option<&str>) -> Self {
+        CreditCardTransformer {
+            key: key.map(|k| k.to_string()),
+        }
+    }
+}
+
+impl Transformer for CreditCardTransformer {
+    fn id(&self) -> &str {
+        "credit-card"
+    }
+
+    fn description(&self) -> &str {
+        "Random credit card number passing the Luhn check"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        let mut rng = seeded_rng(self.key.as_deref(), ctx.value);
+        let (prefix, len) = ISSUERS[rng.gen_range(0..ISSUERS.len())];
+
+        let mut digits = prefix.to_vec();
+        while digits.len() < len - 1 {
+            digits.push(rng.gen_range(0..10));
+        }
+        digits.push(luhn::check_digit(&digits));
+
+        luhn::to_string(&digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let transformer = CreditCardTransformer::new(Some("secret"));
+        for i in 0..100 {
+            let input = (1000000000 + i).to_string();
+            let card = transform_value(&transformer, &input);
+            assert_eq!(card, transform_value(&transformer, &input));
+
+            let digits: Vec<u32> = card.chars().map(|c| c.to_digit(10).unwrap()).collect();
+            let (check, digits) = digits.split_last().unwrap();
+            assert_eq!(luhn::check_digit(digits), *check);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::{TransformationContext, Transformer};
+
+enum Element {
+    Null,
+    Value(String),
+    Array(Vec<Element>),
+}
+
+pub struct EachTransformer {
+    transformer: Box<dyn Transformer>,
+    delimiter: Option<String>,
+}
+
+impl EachTransformer {
+    pub fn new(transformer: Box<dyn Transformer>, delimiter: Option<String>) -> Self {
+        EachTransformer {
+            transformer,
+            delimiter,
+        }
+    }
+
+    fn transform_element(&self, element: &str, ctx: &TransformationContext) -> String {
+        self.transformer.transform(&ctx.with_value(element))
+    }
+
+    fn transform_array(&self, array: Vec<Element>, ctx: &TransformationContext) -> Vec<Element> {
+        array
+            .into_iter()
+            .map(|element| match element {
+                Element::Null => Element::Null,
+                Element::Value(value) => Element::Value(self.transform_element(&value, ctx)),
+                Element::Array(array) => Element::Array(self.transform_array(array, ctx)),
+            })
+            .collect()
+    }
+}
+
+/// Parses a Postgres array literal like `{a,"b c",NULL,{d}}`
+fn parse_array(chars: &mut Peekable<Chars>) -> Option<Vec<Element>> {
+    if chars.next()? != '{' {
+        return None;
+    }
+
+    let mut elements = vec![];
+    loop {
+        while chars.peek()?.is_whitespace() {
+            chars.next();
+        }
+        match chars.peek()? {
+            '}' if elements.is_empty() => {
+                chars.next();
+                return Some(elements);
+            }
+            '{' => elements.push(Element::Array(parse_array(chars)?)),
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => value.push(chars.next()?),
+                        c => value.push(c),
+                    }
+                }
+                elements.push(Element::Value(value));
+            }
+            _ => {
+                let mut value = String::new();
+                while !matches!(chars.peek()?, ',' | '}') {
+                    value.push(chars.next()?);
+                }
+                let value = value.trim();
+                if value.eq_ignore_ascii_case("NULL") {
+                    elements.push(Element::Null);
+                } else {
+                    elements.push(Element::Value(value.to_string()));
+                }
+            }
+        }
+
+        while chars.peek()?.is_whitespace() {
+            chars.next();
+        }
+        match chars.next()? {
+            ',' => continue,
+            '}' => return Some(elements),
+            _ => return None,
+        }
+    }
+}
+
+fn format_array(array: &[Element]) -> String {
+    let elements: Vec<String> = array
+        .iter()
+        .map(|element| match element {
+            Element::Null => "NULL".to_string(),
+            Element::Array(array) => format_array(array),
+            Element::Value(value) => {
+                let quote = value.is_empty()
+                    || value.eq_ignore_ascii_case("NULL")
+                    || value
+                        .chars()
+                        .any(|c| c.is_whitespace() || "{},\"\\".contains(c));
+                if quote {
+                    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+                } else {
+                    value.clone()
+                }
+            }
+        })
+        .collect();
+    format!("{{{}}}", elements.join(","))
+}
+
+impl Transformer for EachTransformer {
+    fn id(&self) -> &str {
+        "each"
+    }
+
+    fn description(&self) -> &str {
+        "Applies a transformer to each element of an array or delimited value"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        if ctx.value.is_empty() {
+            return "".to_string();
+        }
+
+        if let Some(delimiter) = &self.delimiter {
+            return ctx
+                .value
+                .split(delimiter.as_str())
+                .map(|element| {
+                    // Keep the spacing around the delimiter, like in "a, b"
+                    let trimmed = element.trim();
+                    let start = element.find(trimmed).unwrap_or(0);
+                    format!(
+                        "{}{}{}",
+                        &element[..start],
+                        self.transform_element(trimmed, ctx),
+                        &element[start + trimmed.len()..]
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(delimiter);
+        }
+
+        let mut chars = ctx.value.trim().chars().peekable();
+        match parse_array(&mut chars) {
+            Some(array) if chars.next().is_none() => {
+                format_array(&self.transform_array(array, ctx))
+            }
+            _ => {
+                log::warn!(
+                    "Row {} doesn't hold an array literal, transforming it as a single value",
+                    ctx.index
+                );
+                self.transform_element(ctx.value, ctx)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::{transform_value, ReverseTransformer};
+
+    #[test]
+    fn test_transform() {
+        let array = EachTransformer::new(Box::new(ReverseTransformer::default()), None);
+        assert_eq!(
+            transform_value(&array, r#"{123,"a b",NULL,"x\"y",{45,6}}"#),
+            r#"{321,"b a",NULL,"y\"x",{54,6}}"#
+        );
+        assert_eq!(transform_value(&array, "{}"), "{}");
+
+        let delimited = EachTransformer::new(
+            Box::new(ReverseTransformer::default()),
+            Some(",".to_string()),
+        );
+        assert_eq!(transform_value(&delimited, "ab@c, de@f"), "c@ba, f@ed");
+        assert_eq!(transform_value(&delimited, ""), "");
+    }
+}
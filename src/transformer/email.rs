@@ -1,11 +1,16 @@
-use super::{TransformationContext, Transformer};
-use fake::{faker::internet::en::FreeEmail, Fake};
+use crate::config::Locale;
 
-pub struct EmailTransformer {}
+use super::{localized, TransformationContext, Transformer};
+
+pub struct EmailTransformer {
+    locale: Locale,
+}
 
 impl EmailTransformer {
-    pub fn default() -> Self {
-        EmailTransformer {}
+    pub fn new(locale: &Locale) -> Self {
+        EmailTransformer {
+            locale: locale.clone(),
+        }
     }
 }
 
@@ -19,6 +24,6 @@ impl Transformer for EmailTransformer {
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
-        FreeEmail().fake()
+        localized!(&self.locale, fake::faker::internet::raw::FreeEmail)
     }
 }
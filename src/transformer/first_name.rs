@@ -1,14 +1,16 @@
-use fake::faker::name::raw::FirstName;
-use fake::locales::EN;
-use fake::Fake;
+use crate::config::Locale;
 
-use super::{TransformationContext, Transformer};
+use super::{localized, TransformationContext, Transformer};
 
-pub struct FirstNameTransformer {}
+pub struct FirstNameTransformer {
+    locale: Locale,
+}
 
 impl FirstNameTransformer {
-    pub fn default() -> Self {
-        FirstNameTransformer {}
+    pub fn new(locale: &Locale) -> Self {
+        FirstNameTransformer {
+            locale: locale.clone(),
+        }
     }
 }
 
@@ -22,6 +24,6 @@ impl Transformer for FirstNameTransformer {
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
-        FirstName(EN).fake()
+        localized!(&self.locale, fake::faker::name::raw::FirstName)
     }
 }
@@ -0,0 +1,153 @@
+use ::fpe::ff1::{FlexibleNumeralString, NumeralStringError, FF1};
+use aes::{Aes128, Aes192, Aes256};
+
+use super::{TransformationContext, Transformer};
+
+enum Cipher {
+    Aes128(FF1<Aes128>),
+    Aes192(FF1<Aes192>),
+    Aes256(FF1<Aes256>),
+}
+
+pub struct FpeTransformer {
+    cipher: Cipher,
+    alphabet: Vec<char>,
+    tweak: Vec<u8>,
+}
+
+impl FpeTransformer {
+    /// Builds a FF1 cipher from an hex encoded AES-128, AES-192 or AES-256 key
+    pub fn new(key: &str, alphabet: &str, tweak: &str) -> Result<Self, String> {
+        let key = decode_hex(key)?;
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let radix = alphabet.len() as u32;
+
+        let cipher = match key.len() {
+            16 => FF1::new(&key, radix).map(Cipher::Aes128),
+            24 => FF1::new(&key, radix).map(Cipher::Aes192),
+            32 => FF1::new(&key, radix).map(Cipher::Aes256),
+            n => return Err(format!("fpe key must have 16, 24 or 32 bytes, got {}", n)),
+        }
+        .map_err(|e| e.to_string())?;
+
+        Ok(FpeTransformer {
+            cipher,
+            alphabet,
+            tweak: tweak.as_bytes().to_vec(),
+        })
+    }
+
+    pub fn encrypt(&self, value: &str) -> Result<String, String> {
+        self.apply(value, false)
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String, String> {
+        self.apply(value, true)
+    }
+
+    /// Encrypts or decrypts the characters that belong to the alphabet,
+    /// leaving any other character (like separators) in place. Values with
+    /// fewer alphabet characters than FF1 accepts are left unchanged.
+    fn apply(&self, value: &str, decrypt: bool) -> Result<String, String> {
+        if value.is_empty() {
+            return Ok("".to_string());
+        }
+
+        let numerals: Vec<u16> = value
+            .chars()
+            .filter_map(|c| self.alphabet.iter().position(|a| *a == c))
+            .map(|i| i as u16)
+            .collect();
+        let numerals = FlexibleNumeralString::from(numerals);
+
+        let result = match (&self.cipher, decrypt) {
+            (Cipher::Aes128(ff), false) => ff.encrypt(&self.tweak, &numerals),
+            (Cipher::Aes128(ff), true) => ff.decrypt(&self.tweak, &numerals),
+            (Cipher::Aes192(ff), false) => ff.encrypt(&self.tweak, &numerals),
+            (Cipher::Aes192(ff), true) => ff.decrypt(&self.tweak, &numerals),
+            (Cipher::Aes256(ff), false) => ff.encrypt(&self.tweak, &numerals),
+            (Cipher::Aes256(ff), true) => ff.decrypt(&self.tweak, &numerals),
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(NumeralStringError::TooShort { ns_len, min_len }) => {
+                log::warn!(
+                    "fpe: value with {} characters of the alphabet left unchanged, at least {} are needed",
+                    ns_len,
+                    min_len
+                );
+                return Ok(value.to_string());
+            }
+            Err(e) => return Err(format!("can't process {}: {}", value, e)),
+        };
+
+        let mut result = Vec::<u16>::from(result).into_iter();
+        Ok(value
+            .chars()
+            .map(|c| {
+                if self.alphabet.contains(&c) {
+                    self.alphabet[result.next().unwrap() as usize]
+                } else {
+                    c
+                }
+            })
+            .collect())
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    if !s.is_ascii() || !s.len().is_multiple_of(2) {
+        return Err("fpe key must be an hex string".to_string());
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|_| "fpe key must be an hex string".to_string())
+        })
+        .collect()
+}
+
+impl Transformer for FpeTransformer {
+    fn id(&self) -> &str {
+        "fpe"
+    }
+
+    fn description(&self) -> &str {
+        "Format-preserving encryption (FF1), reversible with the key"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        self.encrypt(ctx.value)
+            .unwrap_or_else(|e| panic!("fpe: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let transformer =
+            FpeTransformer::new("2b7e151628aed2a6abf7158809cf4f3c", "0123456789", "mrn").unwrap();
+
+        let encrypted = transform_value(&transformer, "123-456-7890");
+        assert_ne!(encrypted, "123-456-7890");
+        assert_eq!(&encrypted[3..4], "-");
+        assert_eq!(&encrypted[7..8], "-");
+        assert_eq!(transformer.decrypt(&encrypted).unwrap(), "123-456-7890");
+    }
+
+    #[test]
+    fn test_short_values() {
+        let transformer =
+            FpeTransformer::new("2b7e151628aed2a6abf7158809cf4f3c", "0123456789", "mrn").unwrap();
+
+        assert_eq!(transform_value(&transformer, "12-345"), "12-345");
+        assert_eq!(transformer.decrypt("12-345").unwrap(), "12-345");
+        assert_eq!(transform_value(&transformer, "N/A"), "N/A");
+        assert_eq!(transformer.decrypt("N/A").unwrap(), "N/A");
+    }
+}
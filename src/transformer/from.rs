@@ -1,13 +1,17 @@
+use crate::config::ValueSource;
+
 use super::{TransformationContext, Transformer};
 
 pub struct FromTransformer {
     column: String,
+    source: ValueSource,
 }
 
 impl FromTransformer {
-    pub fn new(column: &str) -> Self {
+    pub fn new(column: &str, source: &ValueSource) -> Self {
         FromTransformer {
             column: column.to_string(),
+            source: source.clone(),
         }
     }
 }
@@ -23,6 +27,9 @@ impl Transformer for FromTransformer {
 
     fn transform(&self, ctx: &TransformationContext) -> String {
         let index = ctx.columns.iter().position(|c| *c == self.column).unwrap();
-        ctx.row.get(index).unwrap().to_string()
+        match self.source {
+            ValueSource::Transformed => ctx.transformed.get(index).unwrap().to_string(),
+            ValueSource::Original => ctx.row.get(index).unwrap().to_string(),
+        }
     }
 }
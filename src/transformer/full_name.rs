@@ -0,0 +1,29 @@
+use crate::config::Locale;
+
+use super::{localized, TransformationContext, Transformer};
+
+pub struct FullNameTransformer {
+    locale: Locale,
+}
+
+impl FullNameTransformer {
+    pub fn new(locale: &Locale) -> Self {
+        FullNameTransformer {
+            locale: locale.clone(),
+        }
+    }
+}
+
+impl Transformer for FullNameTransformer {
+    fn id(&self) -> &str {
+        "full-name"
+    }
+
+    fn description(&self) -> &str {
+        "Random full name"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        localized!(&self.locale, fake::faker::name::raw::Name)
+    }
+}
@@ -0,0 +1,24 @@
+use super::{TransformationContext, Transformer};
+use fake::{faker::internet::en::IPv4, Fake};
+
+pub struct Ipv4Transformer {}
+
+impl Ipv4Transformer {
+    pub fn default() -> Self {
+        Ipv4Transformer {}
+    }
+}
+
+impl Transformer for Ipv4Transformer {
+    fn id(&self) -> &str {
+        "ipv4"
+    }
+
+    fn description(&self) -> &str {
+        "Random IPv4 address"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        IPv4().fake()
+    }
+}
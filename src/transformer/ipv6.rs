@@ -0,0 +1,24 @@
+use super::{TransformationContext, Transformer};
+use fake::{faker::internet::en::IPv6, Fake};
+
+pub struct Ipv6Transformer {}
+
+impl Ipv6Transformer {
+    pub fn default() -> Self {
+        Ipv6Transformer {}
+    }
+}
+
+impl Transformer for Ipv6Transformer {
+    fn id(&self) -> &str {
+        "ipv6"
+    }
+
+    fn description(&self) -> &str {
+        "Random IPv6 address"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        IPv6().fake()
+    }
+}
@@ -0,0 +1,29 @@
+use crate::config::Locale;
+
+use super::{localized, TransformationContext, Transformer};
+
+pub struct JobTitleTransformer {
+    locale: Locale,
+}
+
+impl JobTitleTransformer {
+    pub fn new(locale: &Locale) -> Self {
+        JobTitleTransformer {
+            locale: locale.clone(),
+        }
+    }
+}
+
+impl Transformer for JobTitleTransformer {
+    fn id(&self) -> &str {
+        "job-title"
+    }
+
+    fn description(&self) -> &str {
+        "Random job title"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        localized!(&self.locale, fake::faker::job::raw::Title)
+    }
+}
@@ -0,0 +1,119 @@
+use serde_json::Value;
+
+use super::{TransformationContext, Transformer};
+
+pub struct JsonTransformer {
+    rules: Vec<(String, Box<dyn Transformer>)>,
+}
+
+impl JsonTransformer {
+    pub fn new(rules: Vec<(String, Box<dyn Transformer>)>) -> Self {
+        JsonTransformer { rules }
+    }
+
+    /// Runs the nested transformer over a single node, keeping the JSON type
+    /// of the node when the result can still be represented by it
+    fn transform_node(
+        &self,
+        transformer: &dyn Transformer,
+        node: Value,
+        ctx: &TransformationContext,
+    ) -> Value {
+        let value = match &node {
+            Value::Null => return node,
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let result = transformer.transform(&ctx.with_value(&value));
+
+        match node {
+            Value::String(_) => Value::String(result),
+            // an empty value is a null in the CSV, as it is in JSON
+            _ if result.is_empty() => Value::Null,
+            _ => serde_json::from_str(&result).unwrap_or(Value::String(result)),
+        }
+    }
+}
+
+impl Transformer for JsonTransformer {
+    fn id(&self) -> &str {
+        "json"
+    }
+
+    fn description(&self) -> &str {
+        "Transforms the nodes of a JSON document matching JSONPath expressions"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        if ctx.value.is_empty() {
+            return "".to_string();
+        }
+
+        let mut document: Value = match serde_json::from_str(ctx.value) {
+            Ok(document) => document,
+            Err(e) => {
+                log::warn!("Row {} has invalid JSON, clearing it: {}", ctx.index, e);
+                return "".to_string();
+            }
+        };
+
+        for (path, transformer) in &self.rules {
+            document = jsonpath_lib::replace_with(document, path, &mut |node| {
+                Some(self.transform_node(transformer.as_ref(), node, ctx))
+            })
+            .unwrap();
+        }
+
+        document.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::{
+        transform_value, NullTransformer, ReverseTransformer, SequenceTransformer,
+    };
+
+    #[test]
+    fn test_transform() {
+        let transformer = JsonTransformer::new(vec![
+            (
+                "$.contact.name".to_string(),
+                Box::new(ReverseTransformer::default()),
+            ),
+            (
+                "$.visits[*].id".to_string(),
+                Box::new(SequenceTransformer::default()),
+            ),
+        ]);
+
+        let value = transform_value(
+            &transformer,
+            r#"{"contact":{"name":"John","age":40},"visits":[{"id":10},{"id":11}]}"#,
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&value).unwrap(),
+            serde_json::json!({"contact": {"name": "nhoJ", "age": 40}, "visits": [{"id": 2}, {"id": 2}]})
+        );
+        assert_eq!(transform_value(&transformer, ""), "");
+        assert_eq!(transform_value(&transformer, "not json"), "");
+    }
+
+    #[test]
+    fn test_keeps_order_and_nulls() {
+        let transformer = JsonTransformer::new(vec![(
+            "$.age".to_string(),
+            Box::new(NullTransformer::default()),
+        )]);
+
+        assert_eq!(
+            transform_value(&transformer, r#"{"name":"John","age":40,"city":"Boston"}"#),
+            r#"{"name":"John","age":null,"city":"Boston"}"#
+        );
+        assert_eq!(
+            transform_value(&transformer, r#"{"zip":"02110","city":"Boston"}"#),
+            r#"{"zip":"02110","city":"Boston"}"#
+        );
+    }
+}
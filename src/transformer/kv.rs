@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use super::{TransformationContext, Transformer};
+
+pub struct KvTransformer {
+    keys: HashMap<String, Box<dyn Transformer>>,
+    pair_separator: String,
+    key_separator: String,
+}
+
+impl KvTransformer {
+    pub fn new(
+        keys: Vec<(String, Box<dyn Transformer>)>,
+        pair_separator: &str,
+        key_separator: &str,
+    ) -> Self {
+        KvTransformer {
+            keys: keys.into_iter().collect(),
+            pair_separator: pair_separator.to_string(),
+            key_separator: key_separator.to_string(),
+        }
+    }
+}
+
+impl Transformer for KvTransformer {
+    fn id(&self) -> &str {
+        "kv"
+    }
+
+    fn description(&self) -> &str {
+        "Transforms the values of selected keys in key=value; strings"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        ctx.value
+            .split(self.pair_separator.as_str())
+            .map(|pair| {
+                let (key, value) = match pair.split_once(self.key_separator.as_str()) {
+                    Some(kv) => kv,
+                    None => return pair.to_string(),
+                };
+                match self.keys.get(key.trim()) {
+                    Some(transformer) => format!(
+                        "{}{}{}",
+                        key,
+                        self.key_separator,
+                        transformer.transform(&ctx.with_value(value))
+                    ),
+                    None => pair.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&self.pair_separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::{transform_value, ReverseTransformer};
+
+    #[test]
+    fn test_transform() {
+        let transformer = KvTransformer::new(
+            vec![("name".to_string(), Box::new(ReverseTransformer::default()))],
+            "|",
+            ":",
+        );
+        assert_eq!(
+            transform_value(&transformer, "id:1|name:John|flag"),
+            "id:1|name:nhoJ|flag"
+        );
+    }
+}
@@ -1,14 +1,16 @@
-use fake::faker::name::raw::LastName;
-use fake::locales::EN;
-use fake::Fake;
+use crate::config::Locale;
 
-use super::{TransformationContext, Transformer};
+use super::{localized, TransformationContext, Transformer};
 
-pub struct LastNameTransformer {}
+pub struct LastNameTransformer {
+    locale: Locale,
+}
 
 impl LastNameTransformer {
-    pub fn default() -> Self {
-        LastNameTransformer {}
+    pub fn new(locale: &Locale) -> Self {
+        LastNameTransformer {
+            locale: locale.clone(),
+        }
     }
 }
 
@@ -22,6 +24,6 @@ impl Transformer for LastNameTransformer {
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
-        LastName(EN).fake()
+        localized!(&self.locale, fake::faker::name::raw::LastName)
     }
 }
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fs;
+
+use super::{TransformationContext, Transformer};
+
+pub struct LookupTransformer {
+    mapping: HashMap<String, String>,
+    fallback: Option<Box<dyn Transformer>>,
+}
+
+impl LookupTransformer {
+    pub fn new(file: &str, fallback: Option<Box<dyn Transformer>>) -> Result<Self, String> {
+        let mapping = load_mapping(file).map_err(|e| format!("lookup file {}: {}", file, e))?;
+        Ok(LookupTransformer { mapping, fallback })
+    }
+
+    pub fn from_mapping(
+        mapping: HashMap<String, String>,
+        fallback: Option<Box<dyn Transformer>>,
+    ) -> Self {
+        LookupTransformer { mapping, fallback }
+    }
+}
+
+/// Loads the value mapping from a YAML dictionary or a two column CSV file
+/// without headers, depending on the file extension
+fn load_mapping(file: &str) -> Result<HashMap<String, String>, String> {
+    if file.ends_with(".yml") || file.ends_with(".yaml") {
+        let contents = fs::read_to_string(file).map_err(|e| e.to_string())?;
+        return serde_yaml::from_str(&contents).map_err(|e| e.to_string());
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(file)
+        .map_err(|e| e.to_string())?;
+    reader
+        .records()
+        .enumerate()
+        .map(|(i, record)| {
+            let record = record.map_err(|e| e.to_string())?;
+            if record.len() != 2 {
+                return Err(format!(
+                    "line {} has {} columns instead of 2",
+                    i + 1,
+                    record.len()
+                ));
+            }
+            Ok((record[0].to_string(), record[1].to_string()))
+        })
+        .collect()
+}
+
+impl Transformer for LookupTransformer {
+    fn id(&self) -> &str {
+        "lookup"
+    }
+
+    fn description(&self) -> &str {
+        "Replacement looked up from a CSV or YAML dictionary"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        match self.mapping.get(ctx.value) {
+            Some(value) => value.clone(),
+            None => match &self.fallback {
+                Some(fallback) => fallback.transform(ctx),
+                None => "".to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::{transform_value, StaticTransformer};
+    use std::io::Write;
+
+    #[test]
+    fn test_transform() {
+        let mut file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+        writeln!(file, "Downtown Clinic,Sunrise Clinic\nNorth Lab,Maple Lab").unwrap();
+
+        let transformer = LookupTransformer::new(
+            file.path().to_str().unwrap(),
+            Some(Box::new(StaticTransformer::new("Other Clinic"))),
+        )
+        .unwrap();
+        assert_eq!(transform_value(&transformer, "North Lab"), "Maple Lab");
+        assert_eq!(transform_value(&transformer, "South Lab"), "Other Clinic");
+    }
+
+    #[test]
+    fn test_invalid_file() {
+        let mut file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+        writeln!(file, "Downtown Clinic,Sunrise Clinic\nNorth Lab").unwrap();
+
+        let error = LookupTransformer::new(file.path().to_str().unwrap(), None)
+            .err()
+            .unwrap();
+        assert!(error.ends_with("line 2 has 1 columns instead of 2"));
+        assert!(LookupTransformer::new("missing.csv", None).is_err());
+        assert!(LookupTransformer::new("missing.yml", None).is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use super::{TransformationContext, Transformer};
+use fake::faker::lorem::en::{Sentences, Words};
+use fake::Fake;
+
+pub struct LoremTransformer {
+    words: usize,
+    sentences: Option<usize>,
+}
+
+impl LoremTransformer {
+    pub fn new(words: usize, sentences: Option<usize>) -> Self {
+        LoremTransformer { words, sentences }
+    }
+}
+
+impl Transformer for LoremTransformer {
+    fn id(&self) -> &str {
+        "lorem"
+    }
+
+    fn description(&self) -> &str {
+        "Random lorem ipsum words or sentences"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        let text: Vec<String> = match self.sentences {
+            Some(count) => Sentences(count..count + 1).fake(),
+            None => Words(self.words..self.words + 1).fake(),
+        };
+        text.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let transformer = LoremTransformer::new(3, None);
+        assert_eq!(transform_value(&transformer, "").split(' ').count(), 3);
+    }
+}
@@ -0,0 +1,25 @@
+/// Luhn check digit for the given digits (without the check digit itself)
+pub fn check_digit(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| {
+            if i % 2 == 0 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                *d
+            }
+        })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+pub fn to_string(digits: &[u32]) -> String {
+    digits.iter().map(|d| d.to_string()).collect()
+}
@@ -0,0 +1,24 @@
+use super::{TransformationContext, Transformer};
+use fake::{faker::internet::en::MACAddress, Fake};
+
+pub struct MacAddressTransformer {}
+
+impl MacAddressTransformer {
+    pub fn default() -> Self {
+        MacAddressTransformer {}
+    }
+}
+
+impl Transformer for MacAddressTransformer {
+    fn id(&self) -> &str {
+        "mac-address"
+    }
+
+    fn description(&self) -> &str {
+        "Random MAC address"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        MACAddress().fake()
+    }
+}
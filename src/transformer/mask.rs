@@ -0,0 +1,107 @@
+use rand::Rng;
+
+use crate::config::MaskPreserve;
+
+use super::{TransformationContext, Transformer};
+
+pub struct MaskTransformer {
+    keep_first: usize,
+    keep_last: usize,
+    mask_char: Option<char>,
+    preserve: Vec<MaskPreserve>,
+}
+
+impl MaskTransformer {
+    pub fn new(
+        keep_first: usize,
+        keep_last: usize,
+        mask_char: Option<char>,
+        preserve: &[MaskPreserve],
+    ) -> Self {
+        MaskTransformer {
+            keep_first,
+            keep_last,
+            mask_char,
+            preserve: preserve.to_vec(),
+        }
+    }
+
+    fn mask(&self, c: char) -> char {
+        let mut rng = rand::thread_rng();
+        if !c.is_alphanumeric() && self.preserve.contains(&MaskPreserve::Punctuation) {
+            return c;
+        }
+        if let Some(mask_char) = self.mask_char {
+            return mask_char;
+        }
+
+        if c.is_ascii_digit() {
+            rng.gen_range('0'..='9')
+        } else if c.is_alphabetic() {
+            let letter = rng.gen_range('a'..='z');
+            if c.is_uppercase() && self.preserve.contains(&MaskPreserve::Case) {
+                letter.to_ascii_uppercase()
+            } else {
+                letter
+            }
+        } else {
+            c
+        }
+    }
+}
+
+impl Transformer for MaskTransformer {
+    fn id(&self) -> &str {
+        "mask"
+    }
+
+    fn description(&self) -> &str {
+        "Masks the value keeping its length and character classes"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        let (value, domain) = match ctx.value.rfind('@') {
+            Some(at) if self.preserve.contains(&MaskPreserve::Domain) => ctx.value.split_at(at),
+            _ => (ctx.value, ""),
+        };
+
+        let len = value.chars().count();
+        let masked: String = value
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if i < self.keep_first || i + self.keep_last >= len {
+                    c
+                } else {
+                    self.mask(c)
+                }
+            })
+            .collect();
+
+        format!("{}{}", masked, domain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let ssn = MaskTransformer::new(0, 4, Some('*'), &[MaskPreserve::Punctuation]);
+        assert_eq!(transform_value(&ssn, "123-45-6789"), "***-**-6789");
+
+        let email = MaskTransformer::new(
+            1,
+            0,
+            None,
+            &[MaskPreserve::Domain, MaskPreserve::Punctuation],
+        );
+        let masked = transform_value(&email, "john.doe@example.com");
+        assert_eq!(masked.len(), 20);
+        assert!(masked.starts_with('j'));
+        assert_eq!(&masked[4..5], ".");
+        assert!(masked.ends_with("@example.com"));
+    }
+}
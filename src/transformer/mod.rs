@@ -1,59 +1,170 @@
 mod city;
+mod company_name;
+mod credit_card;
 mod date;
+mod each;
 mod email;
 mod first_name;
+mod fpe;
 mod from;
+mod full_name;
+mod ipv4;
+mod ipv6;
+mod job_title;
+mod json;
+mod kv;
 mod last_name;
+mod lookup;
+mod lorem;
+mod luhn;
+mod mac_address;
+mod mask;
+mod noise;
+mod npi;
 mod null;
+mod phone;
+mod phone_number;
 mod random;
 mod random_value;
+mod redact;
 mod regex;
 mod reverse;
+mod secondary_address;
 mod sequence;
+mod ssn;
 mod state;
 mod statictr;
 mod street;
+mod template;
+mod url;
+mod username;
+mod uuid;
+mod xml;
 mod zip_code;
 
 pub use self::city::CityTransformer;
+pub use self::company_name::CompanyNameTransformer;
+pub use self::credit_card::CreditCardTransformer;
 pub use self::date::DateTransformer;
+pub use self::each::EachTransformer;
 pub use self::email::EmailTransformer;
 pub use self::first_name::FirstNameTransformer;
+pub use self::fpe::FpeTransformer;
 pub use self::from::FromTransformer;
+pub use self::full_name::FullNameTransformer;
+pub use self::ipv4::Ipv4Transformer;
+pub use self::ipv6::Ipv6Transformer;
+pub use self::job_title::JobTitleTransformer;
+pub use self::json::JsonTransformer;
+pub use self::kv::KvTransformer;
 pub use self::last_name::LastNameTransformer;
+pub use self::lookup::LookupTransformer;
+pub use self::lorem::LoremTransformer;
+pub use self::mac_address::MacAddressTransformer;
+pub use self::mask::MaskTransformer;
+pub use self::noise::NoiseTransformer;
+pub use self::npi::NpiTransformer;
 pub use self::null::NullTransformer;
+pub use self::phone::PhoneTransformer;
+pub use self::phone_number::PhoneNumberTransformer;
 pub use self::random::RandomTransformer;
 pub use self::random_value::RandomValueTransformer;
+pub use self::redact::RedactTransformer;
 pub use self::regex::RegexTransformer;
 pub use self::reverse::ReverseTransformer;
+pub use self::secondary_address::SecondaryAddressTransformer;
 pub use self::sequence::SequenceTransformer;
+pub use self::ssn::SsnTransformer;
 pub use self::state::StateTransformer;
 pub use self::statictr::StaticTransformer;
 pub use self::street::StreetTransformer;
+pub use self::template::TemplateTransformer;
+pub use self::url::UrlTransformer;
+pub use self::username::UsernameTransformer;
+pub use self::uuid::UuidTransformer;
+pub use self::xml::XmlTransformer;
 pub use self::zip_code::ZipCodeTransformer;
 
+use std::collections::HashMap;
+
+use hmac::{Hmac, Mac};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use sha2::Sha256;
+
+use crate::config::{
+    Locale, NoiseDistribution, Number, RandomType, RedactReplacement, ValueSource,
+};
+
 pub fn transformers_info() -> Vec<(String, String)> {
     let all: Vec<Box<dyn Transformer>> = vec![
         Box::new(NullTransformer::default()),
         Box::new(ReverseTransformer::default()),
-        Box::new(FirstNameTransformer::default()),
-        Box::new(LastNameTransformer::default()),
+        Box::new(FirstNameTransformer::new(&Locale::default())),
+        Box::new(LastNameTransformer::new(&Locale::default())),
         Box::new(SequenceTransformer::default()),
-        Box::new(EmailTransformer::default()),
-        Box::new(StreetTransformer::default()),
-        Box::new(CityTransformer::default()),
-        Box::new(StateTransformer::default()),
-        Box::new(ZipCodeTransformer::default()),
+        Box::new(EmailTransformer::new(&Locale::default())),
+        Box::new(StreetTransformer::new(&Locale::default())),
+        Box::new(CityTransformer::new(&Locale::default())),
+        Box::new(StateTransformer::new(&Locale::default())),
+        Box::new(ZipCodeTransformer::new(&Locale::default())),
         Box::new(RegexTransformer::new("[a]")),
-        Box::new(FromTransformer::new("source")),
+        Box::new(FromTransformer::new("source", &ValueSource::Transformed)),
         Box::new(DateTransformer::new("%Y-%m-%d")),
-        Box::new(RandomTransformer::new(&1, &100)),
-        Box::new(RandomValueTransformer::new(&vec![
-            "value1".to_owned(),
-            "value2".to_owned(),
-            "value3".to_owned(),
-        ])),
+        Box::new(RandomTransformer::new(
+            &RandomType::Int,
+            &Number::from(1),
+            &Number::from(100),
+            None,
+        )),
+        Box::new(
+            RandomValueTransformer::new(
+                &[
+                    "value1".to_owned(),
+                    "value2".to_owned(),
+                    "value3".to_owned(),
+                ],
+                &None,
+            )
+            .unwrap(),
+        ),
         Box::new(StaticTransformer::new("static")),
+        Box::new(TemplateTransformer::new("{{first_name}}").unwrap()),
+        Box::new(LookupTransformer::from_mapping(HashMap::new(), None)),
+        Box::new(MaskTransformer::new(0, 4, Some('*'), &[])),
+        Box::new(FpeTransformer::new(&"00".repeat(16), "0123456789", "").unwrap()),
+        Box::new(NpiTransformer::new(None)),
+        Box::new(CreditCardTransformer::new(None)),
+        Box::new(SsnTransformer::new(None)),
+        Box::new(PhoneTransformer::new(None)),
+        Box::new(NoiseTransformer::new(
+            &NoiseDistribution::Gaussian,
+            1.0,
+            false,
+            None,
+            None,
+            None,
+        )),
+        Box::new(PhoneNumberTransformer::new(&Locale::default())),
+        Box::new(FullNameTransformer::new(&Locale::default())),
+        Box::new(CompanyNameTransformer::new(&Locale::default())),
+        Box::new(SecondaryAddressTransformer::new(&Locale::default())),
+        Box::new(UsernameTransformer::new(&Locale::default())),
+        Box::new(JobTitleTransformer::new(&Locale::default())),
+        Box::new(Ipv4Transformer::default()),
+        Box::new(Ipv6Transformer::default()),
+        Box::new(MacAddressTransformer::default()),
+        Box::new(UrlTransformer::default()),
+        Box::new(UuidTransformer::default()),
+        Box::new(LoremTransformer::new(5, None)),
+        Box::new(RedactTransformer::new(&[], &[], &[], &RedactReplacement::Placeholder).unwrap()),
+        Box::new(JsonTransformer::new(vec![])),
+        Box::new(XmlTransformer::new(vec![], HashMap::new()).unwrap()),
+        Box::new(KvTransformer::new(vec![], ";", "=")),
+        Box::new(EachTransformer::new(
+            Box::new(NullTransformer::default()),
+            None,
+        )),
     ];
     let mut res: Vec<(String, String)> = all
         .iter()
@@ -63,6 +174,69 @@ pub fn transformers_info() -> Vec<(String, String)> {
     res
 }
 
+/// Generates a fake value from a `fake::faker::*::raw` faker in the given locale
+macro_rules! localized {
+    ($locale:expr, $faker:path) => {{
+        use crate::config::Locale;
+        use fake::locales::{AR_SA, EN, FR_FR, JA_JP, PT_BR, ZH_CN, ZH_TW};
+        use fake::Fake;
+        use $faker as faker;
+
+        match $locale {
+            Locale::En => faker(EN).fake(),
+            Locale::FrFr => faker(FR_FR).fake(),
+            Locale::PtBr => faker(PT_BR).fake(),
+            Locale::ArSa => faker(AR_SA).fake(),
+            Locale::JaJp => faker(JA_JP).fake(),
+            Locale::ZhCn => faker(ZH_CN).fake(),
+            Locale::ZhTw => faker(ZH_TW).fake(),
+        }
+    }};
+}
+pub(crate) use localized;
+
+/// Random generator seeded from the original value when a key is given, so
+/// the same input always produces the same output. The seed is a HMAC of the
+/// value, so the output can't be matched with guessed inputs without the key.
+pub fn seeded_rng(key: Option<&str>, value: &str) -> XorShiftRng {
+    let key = match key {
+        Some(key) => key,
+        None => return XorShiftRng::from_rng(rand::thread_rng()).unwrap(),
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).unwrap();
+    mac.update(value.as_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let mut seed = [0u8; 16];
+    seed.copy_from_slice(&digest[..16]);
+    XorShiftRng::from_seed(seed)
+}
+
+/// Transforms a value as the only column of a row
+#[cfg(test)]
+pub fn transform_value(transformer: &dyn Transformer, value: &str) -> String {
+    transform_column(transformer, &["value"], &[value], 0)
+}
+
+/// Transforms the value at `column` of a row with the given columns
+#[cfg(test)]
+pub fn transform_column(
+    transformer: &dyn Transformer,
+    columns: &[&str],
+    row: &[&str],
+    column: usize,
+) -> String {
+    let transformed: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+    transformer.transform(&TransformationContext {
+        index: 1,
+        row: row.to_vec(),
+        transformed: &transformed,
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+        value: row[column],
+    })
+}
+
 pub trait Transformer {
     fn id(&self) -> &str;
     fn description(&self) -> &str;
@@ -71,7 +245,24 @@ pub trait Transformer {
 
 pub struct TransformationContext<'a> {
     pub index: usize,
+    /// Original values of the row
     pub row: Vec<&'a str>,
+    /// Values of the row transformed so far, columns not yet transformed
+    /// hold their original value
+    pub transformed: &'a [String],
     pub columns: Vec<String>,
     pub value: &'a str,
 }
+
+impl<'a> TransformationContext<'a> {
+    /// Context for a nested transformer working on a part of the value
+    pub fn with_value<'b>(&'b self, value: &'b str) -> TransformationContext<'b> {
+        TransformationContext {
+            index: self.index,
+            row: self.row.clone(),
+            transformed: self.transformed,
+            columns: self.columns.clone(),
+            value,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+
+use crate::config::NoiseDistribution;
+
+use super::{TransformationContext, Transformer};
+
+pub struct NoiseTransformer {
+    distribution: NoiseDistribution,
+    scale: f64,
+    relative: bool,
+    min: Option<f64>,
+    max: Option<f64>,
+    precision: Option<usize>,
+}
+
+impl NoiseTransformer {
+    pub fn new(
+        distribution: &NoiseDistribution,
+        scale: f64,
+        relative: bool,
+        min: Option<f64>,
+        max: Option<f64>,
+        precision: Option<usize>,
+    ) -> Self {
+        NoiseTransformer {
+            distribution: distribution.clone(),
+            scale,
+            relative,
+            min,
+            max,
+            precision,
+        }
+    }
+
+    fn sample(&self, scale: f64) -> f64 {
+        let mut rng = rand::thread_rng();
+        match self.distribution {
+            NoiseDistribution::Gaussian => Normal::new(0.0, scale).unwrap().sample(&mut rng),
+            NoiseDistribution::Laplace => {
+                let u: f64 = rng.gen_range(-0.5..0.5);
+                -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+            }
+        }
+    }
+}
+
+impl Transformer for NoiseTransformer {
+    fn id(&self) -> &str {
+        "noise"
+    }
+
+    fn description(&self) -> &str {
+        "Numeric value perturbed with random noise"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        if ctx.value.is_empty() {
+            return "".to_string();
+        }
+
+        let value: f64 = match ctx.value.trim().parse() {
+            Ok(value) => value,
+            Err(_) => {
+                log::warn!("Non-numeric value for noise transformer, replacing with null");
+                return "".to_string();
+            }
+        };
+
+        let scale = if self.relative {
+            (self.scale * value).abs()
+        } else {
+            self.scale
+        };
+        let mut noisy = if scale > 0.0 {
+            value + self.sample(scale)
+        } else {
+            value
+        };
+
+        if let Some(min) = self.min {
+            noisy = noisy.max(min);
+        }
+        if let Some(max) = self.max {
+            noisy = noisy.min(max);
+        }
+
+        let precision = self.precision.unwrap_or_else(|| {
+            ctx.value
+                .trim()
+                .split_once('.')
+                .map_or(0, |(_, decimals)| decimals.len())
+        });
+        format!("{:.*}", precision, noisy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let transformer = NoiseTransformer::new(
+            &NoiseDistribution::Laplace,
+            0.1,
+            true,
+            Some(0.0),
+            Some(10.0),
+            None,
+        );
+        assert_eq!(transform_value(&transformer, "-125.50"), "0.00");
+        assert_eq!(transform_value(&transformer, ""), "");
+    }
+}
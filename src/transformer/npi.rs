@@ -0,0 +1,67 @@
+use rand::Rng;
+
+use super::{luhn, seeded_rng, TransformationContext, Transformer};
+
+pub struct NpiTransformer {
+    key: Option<String>,
+}
+
+impl NpiTransformer {
+    pub fn new(key: Option<&str>) -> Self {
+        NpiTransformer {
+            key: key.map(|k| k.to_string()),
+        }
+    }
+}
+
+impl Transformer for NpiTransformer {
+    fn id(&self) -> &str {
+        "npi"
+    }
+
+    fn description(&self) -> &str {
+        "Random National Provider Identifier with a valid check digit"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        let mut rng = seeded_rng(self.key.as_deref(), ctx.value);
+        let mut digits: Vec<u32> = vec![rng.gen_range(1..=2)];
+        digits.extend((0..8).map(|_| rng.gen_range(0..10)));
+
+        // The check digit is computed as if the number was prefixed by 80840
+        let prefixed: Vec<u32> = [8, 0, 8, 4, 0].iter().chain(&digits).copied().collect();
+        digits.push(luhn::check_digit(&prefixed));
+
+        luhn::to_string(&digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let transformer = NpiTransformer::new(Some("secret"));
+        for i in 0..100 {
+            let input = (1000000000 + i).to_string();
+            let npi = transform_value(&transformer, &input);
+            assert_eq!(npi, transform_value(&transformer, &input));
+
+            let digits: Vec<u32> = "80840"
+                .chars()
+                .chain(npi.chars())
+                .map(|c| c.to_digit(10).unwrap())
+                .collect();
+            assert_eq!(digits.len(), 15);
+            assert_eq!(luhn::check_digit(&digits[..14]), digits[14]);
+        }
+
+        let other_key = NpiTransformer::new(Some("other"));
+        assert_ne!(
+            transform_value(&transformer, "1234567890"),
+            transform_value(&other_key, "1234567890")
+        );
+    }
+}
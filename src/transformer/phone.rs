@@ -0,0 +1,64 @@
+use rand::Rng;
+
+use super::{seeded_rng, TransformationContext, Transformer};
+
+pub struct PhoneTransformer {
+    key: Option<String>,
+}
+
+impl PhoneTransformer {
+    pub fn new(key: Option<&str>) -> Self {
+        PhoneTransformer {
+            key: key.map(|k| k.to_string()),
+        }
+    }
+}
+
+/// Three digit NANP code starting with 2-9 that isn't a N11 service code
+fn nxx(rng: &mut impl Rng, second: std::ops::Range<u32>) -> u32 {
+    loop {
+        let code = rng.gen_range(2..10) * 100 + rng.gen_range(second.clone()) * 10;
+        let code = code + rng.gen_range(0..10);
+        if code % 100 != 11 {
+            return code;
+        }
+    }
+}
+
+impl Transformer for PhoneTransformer {
+    fn id(&self) -> &str {
+        "phone"
+    }
+
+    fn description(&self) -> &str {
+        "Random US phone number following the NANP rules"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        let mut rng = seeded_rng(self.key.as_deref(), ctx.value);
+
+        // Area codes with 9 as the middle digit are reserved
+        let area = nxx(&mut rng, 0..9);
+        let exchange = nxx(&mut rng, 0..10);
+        let line = rng.gen_range(0..10000);
+
+        format!("{}-{}-{:04}", area, exchange, line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let transformer = PhoneTransformer::new(None);
+        for _ in 0..100 {
+            let phone = transform_value(&transformer, "555-123-4567");
+            assert_eq!(phone.len(), 12);
+            assert_ne!(&phone[1..3], "11");
+            assert_ne!(&phone[5..7], "11");
+        }
+    }
+}
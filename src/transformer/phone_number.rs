@@ -0,0 +1,29 @@
+use crate::config::Locale;
+
+use super::{localized, TransformationContext, Transformer};
+
+pub struct PhoneNumberTransformer {
+    locale: Locale,
+}
+
+impl PhoneNumberTransformer {
+    pub fn new(locale: &Locale) -> Self {
+        PhoneNumberTransformer {
+            locale: locale.clone(),
+        }
+    }
+}
+
+impl Transformer for PhoneNumberTransformer {
+    fn id(&self) -> &str {
+        "phone-number"
+    }
+
+    fn description(&self) -> &str {
+        "Random phone number"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        localized!(&self.locale, fake::faker::phone_number::raw::PhoneNumber)
+    }
+}
@@ -1,17 +1,43 @@
 use super::{TransformationContext, Transformer};
+use crate::config::{Number, RandomType};
 use rand::Rng;
 
+enum Range {
+    Int(i128, i128),
+    Float(f64, f64, Option<u32>),
+    Decimal(i128, i128, u32),
+}
+
 pub struct RandomTransformer {
-    range_start: usize,
-    range_end: usize,
+    range: Range,
 }
 
 impl RandomTransformer {
-    pub fn new(range_start: &usize, range_end: &usize) -> Self {
-        RandomTransformer {
-            range_start: *range_start,
-            range_end: *range_end,
-        }
+    pub fn new(
+        kind: &RandomType,
+        range_start: &Number,
+        range_end: &Number,
+        scale: Option<u32>,
+    ) -> Self {
+        let range = match kind {
+            RandomType::Int => {
+                Range::Int(range_start.to_int().unwrap(), range_end.to_int().unwrap())
+            }
+            RandomType::Float => Range::Float(
+                range_start.to_float().unwrap(),
+                range_end.to_float().unwrap(),
+                scale,
+            ),
+            RandomType::Decimal => {
+                let scale = scale.unwrap_or(0);
+                Range::Decimal(
+                    range_start.to_scaled(scale).unwrap(),
+                    range_end.to_scaled(scale).unwrap(),
+                    scale,
+                )
+            }
+        };
+        RandomTransformer { range }
     }
 }
 
@@ -21,12 +47,49 @@ impl Transformer for RandomTransformer {
     }
 
     fn description(&self) -> &str {
-        "Random value defined by a range"
+        "Random integer, float or decimal value defined by a range"
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
         let mut rng = rand::thread_rng();
-        let random_value = rng.gen_range(self.range_start..self.range_end);
-        random_value.to_string()
+        match self.range {
+            Range::Int(start, end) => rng.gen_range(start..end).to_string(),
+            Range::Float(start, end, None) => rng.gen_range(start..end).to_string(),
+            Range::Float(start, end, Some(scale)) => {
+                format!("{:.*}", scale as usize, rng.gen_range(start..end))
+            }
+            Range::Decimal(start, end, scale) => {
+                let value = rng.gen_range(start..end);
+                if scale == 0 {
+                    return value.to_string();
+                }
+                let divisor = 10i128.pow(scale);
+                let sign = if value < 0 { "-" } else { "" };
+                format!(
+                    "{}{}.{:0width$}",
+                    sign,
+                    (value / divisor).abs(),
+                    (value % divisor).abs(),
+                    width = scale as usize
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_decimal() {
+        let transformer = RandomTransformer::new(
+            &RandomType::Decimal,
+            &Number("-0.99".to_string()),
+            &Number("-0.98".to_string()),
+            Some(2),
+        );
+        assert_eq!(transform_value(&transformer, "12"), "-0.99");
     }
 }
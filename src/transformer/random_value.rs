@@ -1,30 +1,82 @@
 use super::{TransformationContext, Transformer};
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
 use rand::Rng;
 
 pub struct RandomValueTransformer {
     values: Vec<String>,
+    weights: Option<WeightedIndex<f64>>,
 }
 
 impl RandomValueTransformer {
-    pub fn new(values: &Vec<String>) -> Self {
-        RandomValueTransformer {
-            values: values.clone(),
+    pub fn new(values: &[String], weights: &Option<Vec<f64>>) -> Result<Self, String> {
+        if values.is_empty() {
+            return Err("random-value needs at least one value".to_string());
         }
+        let weights = match weights {
+            Some(weights) if weights.len() != values.len() => {
+                return Err(format!(
+                    "random-value needs one weight per value, got {} weights for {} values",
+                    weights.len(),
+                    values.len()
+                ))
+            }
+            Some(weights) => Some(
+                WeightedIndex::new(weights)
+                    .map_err(|e| format!("random-value weights {:?}: {}", weights, e))?,
+            ),
+            None => None,
+        };
+
+        Ok(RandomValueTransformer {
+            values: values.to_vec(),
+            weights,
+        })
     }
 }
 
 impl Transformer for RandomValueTransformer {
     fn id(&self) -> &str {
-        "random-values"
+        "random-value"
     }
 
     fn description(&self) -> &str {
-        "Random value picked from a list"
+        "Random value picked from a list, optionally weighted"
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
         let mut rng = rand::thread_rng();
-        let random_value = rng.gen_range(0..self.values.len());
+        let random_value = match &self.weights {
+            Some(weights) => weights.sample(&mut rng),
+            None => rng.gen_range(0..self.values.len()),
+        };
         self.values[random_value].clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_weights() {
+        let transformer = RandomValueTransformer::new(
+            &["completed".to_string(), "cancelled".to_string()],
+            &Some(vec![1.0, 0.0]),
+        )
+        .unwrap();
+        for _ in 0..20 {
+            assert_eq!(transform_value(&transformer, "pending"), "completed");
+        }
+    }
+
+    #[test]
+    fn test_invalid_weights() {
+        let values = ["a".to_string(), "b".to_string()];
+        assert!(RandomValueTransformer::new(&values, &Some(vec![1.0])).is_err());
+        assert!(RandomValueTransformer::new(&values, &Some(vec![0.0, 0.0])).is_err());
+        assert!(RandomValueTransformer::new(&values, &Some(vec![1.0, -1.0])).is_err());
+        assert!(RandomValueTransformer::new(&[], &None).is_err());
+    }
+}
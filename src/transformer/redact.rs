@@ -0,0 +1,255 @@
+use aho_corasick::{AhoCorasickBuilder, MatchKind};
+use chrono::{DateTime, Duration, Utc};
+use fake::faker::chrono::en::DateTimeBetween;
+use fake::faker::internet::en::FreeEmail;
+use fake::Fake;
+use rand::Rng;
+use regex::{Captures, Regex};
+
+use crate::config::{RedactDetector, RedactPattern, RedactReplacement};
+
+use super::{PhoneTransformer, SsnTransformer, TransformationContext, Transformer};
+
+enum Kind {
+    Detector(RedactDetector),
+    /// Custom pattern, with the generator of its fake values when faking
+    Pattern(String, Option<rand_regex::Regex>),
+}
+
+pub struct RedactTransformer {
+    detectors: Vec<(Kind, Regex)>,
+    name_columns: Vec<String>,
+    replace: RedactReplacement,
+}
+
+impl RedactTransformer {
+    pub fn new(
+        detectors: &[RedactDetector],
+        patterns: &[RedactPattern],
+        name_columns: &[String],
+        replace: &RedactReplacement,
+    ) -> Result<Self, String> {
+        let mut all: Vec<(Kind, Regex)> = detectors
+            .iter()
+            .map(|d| (Kind::Detector(d.clone()), Regex::new(d.pattern()).unwrap()))
+            .collect();
+        for pattern in patterns {
+            let regex = Regex::new(&pattern.regex)
+                .map_err(|e| format!("redact pattern {}: {}", pattern.name, e))?;
+            let generator = match replace {
+                RedactReplacement::Fake => {
+                    Some(rand_regex::Regex::compile(&pattern.regex, 1).map_err(|e| {
+                        format!(
+                            "redact pattern {} can't generate fake values: {}",
+                            pattern.name, e
+                        )
+                    })?)
+                }
+                RedactReplacement::Placeholder => None,
+            };
+            all.push((Kind::Pattern(pattern.name.to_uppercase(), generator), regex));
+        }
+
+        Ok(RedactTransformer {
+            detectors: all,
+            name_columns: name_columns.to_vec(),
+            replace: replace.clone(),
+        })
+    }
+
+    fn replacement(&self, kind: &Kind, found: &str, ctx: &TransformationContext) -> String {
+        match (kind, &self.replace) {
+            (Kind::Detector(detector), RedactReplacement::Placeholder) => {
+                format!("[{}]", detector.placeholder())
+            }
+            (Kind::Pattern(_, Some(generator)), _) => rand::thread_rng().sample(generator),
+            (Kind::Pattern(name, None), _) => format!("[{}]", name),
+            (Kind::Detector(RedactDetector::Email), RedactReplacement::Fake) => FreeEmail().fake(),
+            (Kind::Detector(RedactDetector::Phone), RedactReplacement::Fake) => {
+                PhoneTransformer::new(None).transform(ctx)
+            }
+            (Kind::Detector(RedactDetector::Ssn), RedactReplacement::Fake) => {
+                SsnTransformer::new(None).transform(ctx)
+            }
+            (Kind::Detector(RedactDetector::Date), RedactReplacement::Fake) => {
+                let range_start = Utc::now() - Duration::days(90 * 365);
+                let range_end = Utc::now();
+                let date: DateTime<Utc> = DateTimeBetween(range_start, range_end).fake();
+                let format = if found.contains('/') {
+                    "%m/%d/%Y"
+                } else {
+                    "%Y-%m-%d"
+                };
+                date.format(format).to_string()
+            }
+        }
+    }
+}
+
+impl Transformer for RedactTransformer {
+    fn id(&self) -> &str {
+        "redact"
+    }
+
+    fn description(&self) -> &str {
+        "Redacts emails, phones, SSNs, dates and row names found in free text"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        let mut text = ctx.value.to_string();
+
+        for (kind, regex) in &self.detectors {
+            text = regex
+                .replace_all(&text, |caps: &Captures| {
+                    self.replacement(kind, &caps[0], ctx)
+                })
+                .to_string();
+        }
+
+        // Names are the original values of other columns in the same row,
+        // replaced by their transformed counterparts when faking, unless the
+        // column isn't transformed and would put the name back
+        let mut names: Vec<&str> = vec![];
+        let mut replacements: Vec<&str> = vec![];
+        for column in &self.name_columns {
+            let index = match ctx.columns.iter().position(|c| c == column) {
+                Some(index) => index,
+                None => continue,
+            };
+            let name = ctx.row[index].trim();
+            if name.chars().count() < 2 || names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                continue;
+            }
+
+            let transformed = ctx.transformed[index].as_str();
+            let replacement = match self.replace {
+                RedactReplacement::Fake if !transformed.trim().eq_ignore_ascii_case(name) => {
+                    transformed
+                }
+                _ => "[NAME]",
+            };
+            names.push(name);
+            replacements.push(replacement);
+        }
+        if names.is_empty() {
+            return text;
+        }
+
+        // Longer names first, so a name isn't replaced by a part of it
+        let matcher = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(true)
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(&names);
+        let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let mut redacted = String::with_capacity(text.len());
+        let mut last = 0;
+        for found in matcher.find_iter(&text) {
+            let (start, end) = (found.start(), found.end());
+            // Only whole words, as `\b` would match them
+            if word(text[..start].chars().next_back()) == word(text[start..].chars().next())
+                || word(text[..end].chars().next_back()) == word(text[end..].chars().next())
+            {
+                continue;
+            }
+            redacted.push_str(&text[last..start]);
+            redacted.push_str(replacements[found.pattern()]);
+            last = end;
+        }
+        redacted.push_str(&text[last..]);
+
+        redacted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::{transform_column, transform_value};
+
+    #[test]
+    fn test_placeholders() {
+        let transformer = RedactTransformer::new(
+            &[
+                RedactDetector::Email,
+                RedactDetector::Ssn,
+                RedactDetector::Phone,
+            ],
+            &[RedactPattern {
+                name: "mrn".to_string(),
+                regex: r"MRN\d{6}".to_string(),
+            }],
+            &["name".to_string()],
+            &RedactReplacement::Placeholder,
+        )
+        .unwrap();
+        let notes = transform_column(
+            &transformer,
+            &["notes", "name"],
+            &[
+                "Called John (555) 123-4567 on 2020-01-31, ssn 123-45-6789, MRN123456, john@mail.com",
+                "John",
+            ],
+            0,
+        );
+        assert_eq!(
+            notes,
+            "Called [NAME] [PHONE] on 2020-01-31, ssn [SSN], [MRN], [EMAIL]"
+        );
+    }
+
+    #[test]
+    fn test_patterns() {
+        let pattern = |regex: &str| RedactPattern {
+            name: "mrn".to_string(),
+            regex: regex.to_string(),
+        };
+        let anchored = [pattern(r"\bMRN\d{6}\b")];
+
+        let placeholder =
+            RedactTransformer::new(&[], &anchored, &[], &RedactReplacement::Placeholder).unwrap();
+        assert_eq!(
+            transform_value(&placeholder, "MRN123456 and XMRN123456"),
+            "[MRN] and XMRN123456"
+        );
+        assert!(RedactTransformer::new(&[], &anchored, &[], &RedactReplacement::Fake).is_err());
+        assert!(RedactTransformer::new(
+            &[],
+            &[pattern(r"MRN(\d")],
+            &[],
+            &RedactReplacement::Placeholder
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_names() {
+        let transformer = RedactTransformer::new(
+            &[],
+            &[],
+            &["first".to_string(), "full".to_string()],
+            &RedactReplacement::Placeholder,
+        )
+        .unwrap();
+        let notes = transform_column(
+            &transformer,
+            &["notes", "first", "full"],
+            &["john smith called, JOHN came", "John", "John Smith"],
+            0,
+        );
+        assert_eq!(notes, "[NAME] called, [NAME] came");
+    }
+
+    #[test]
+    fn test_untransformed_names_when_faking() {
+        let transformer =
+            RedactTransformer::new(&[], &[], &["name".to_string()], &RedactReplacement::Fake)
+                .unwrap();
+        let notes = transform_column(
+            &transformer,
+            &["notes", "name"],
+            &["Called John, johnny and JOHN", "John"],
+            0,
+        );
+        assert_eq!(notes, "Called [NAME], johnny and [NAME]");
+    }
+}
@@ -0,0 +1,29 @@
+use crate::config::Locale;
+
+use super::{localized, TransformationContext, Transformer};
+
+pub struct SecondaryAddressTransformer {
+    locale: Locale,
+}
+
+impl SecondaryAddressTransformer {
+    pub fn new(locale: &Locale) -> Self {
+        SecondaryAddressTransformer {
+            locale: locale.clone(),
+        }
+    }
+}
+
+impl Transformer for SecondaryAddressTransformer {
+    fn id(&self) -> &str {
+        "secondary-address"
+    }
+
+    fn description(&self) -> &str {
+        "Random secondary address line (apartment, suite)"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        localized!(&self.locale, fake::faker::address::raw::SecondaryAddress)
+    }
+}
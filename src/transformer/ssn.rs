@@ -0,0 +1,55 @@
+use rand::Rng;
+
+use super::{seeded_rng, TransformationContext, Transformer};
+
+pub struct SsnTransformer {
+    key: Option<String>,
+}
+
+impl SsnTransformer {
+    pub fn new(key: Option<&str>) -> Self {
+        SsnTransformer {
+            key: key.map(|k| k.to_string()),
+        }
+    }
+}
+
+impl Transformer for SsnTransformer {
+    fn id(&self) -> &str {
+        "ssn"
+    }
+
+    fn description(&self) -> &str {
+        "Random SSN following the area, group and serial number rules"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        let mut rng = seeded_rng(self.key.as_deref(), ctx.value);
+
+        // Areas 000, 666 and 900-999 are never assigned
+        let mut area = rng.gen_range(1..899);
+        if area >= 666 {
+            area += 1;
+        }
+        let group = rng.gen_range(1..=99);
+        let serial = rng.gen_range(1..=9999);
+
+        format!("{:03}-{:02}-{:04}", area, group, serial)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let transformer = SsnTransformer::new(None);
+        for _ in 0..100 {
+            let ssn = transform_value(&transformer, "123-45-6789");
+            let area: u32 = ssn[0..3].parse().unwrap();
+            assert!(area > 0 && area != 666 && area < 900);
+        }
+    }
+}
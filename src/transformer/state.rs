@@ -1,11 +1,16 @@
-use super::{TransformationContext, Transformer};
-use fake::{faker::address::en::StateAbbr, Fake};
+use crate::config::Locale;
 
-pub struct StateTransformer {}
+use super::{localized, TransformationContext, Transformer};
+
+pub struct StateTransformer {
+    locale: Locale,
+}
 
 impl StateTransformer {
-    pub fn default() -> Self {
-        StateTransformer {}
+    pub fn new(locale: &Locale) -> Self {
+        StateTransformer {
+            locale: locale.clone(),
+        }
     }
 }
 
@@ -19,6 +24,6 @@ impl Transformer for StateTransformer {
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
-        StateAbbr().fake()
+        localized!(&self.locale, fake::faker::address::raw::StateAbbr)
     }
 }
@@ -1,12 +1,17 @@
-use super::{TransformationContext, Transformer};
-use fake::{faker::address::en::StreetName, Fake};
+use crate::config::Locale;
+
+use super::{localized, TransformationContext, Transformer};
 use rand::Rng;
 
-pub struct StreetTransformer {}
+pub struct StreetTransformer {
+    locale: Locale,
+}
 
 impl StreetTransformer {
-    pub fn default() -> Self {
-        StreetTransformer {}
+    pub fn new(locale: &Locale) -> Self {
+        StreetTransformer {
+            locale: locale.clone(),
+        }
     }
 }
 
@@ -20,7 +25,7 @@ impl Transformer for StreetTransformer {
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
-        let street: String = StreetName().fake();
+        let street: String = localized!(&self.locale, fake::faker::address::raw::StreetName);
         let mut rng = rand::thread_rng();
         let random_num = rng.gen_range(20..50000);
 
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use fake::faker::address::en::{CityName, StateAbbr, StreetName, ZipCode};
+use fake::faker::internet::en::FreeEmail;
+use fake::faker::name::raw::{FirstName, LastName};
+use fake::locales::EN;
+use fake::Fake;
+use handlebars::template::{HelperTemplate, Parameter, TemplateElement};
+use handlebars::{
+    handlebars_helper, Context, Handlebars, Helper, HelperResult, JsonValue as Json, Output, Path,
+    RenderContext, RenderError, Template,
+};
+
+use super::{TransformationContext, Transformer};
+
+pub struct TemplateTransformer {
+    handlebars: Handlebars<'static>,
+}
+
+impl TemplateTransformer {
+    pub fn new(template: &str) -> Result<Self, String> {
+        let template =
+            Template::compile(template).map_err(|e| format!("invalid template: {}", e))?;
+        for helper in helpers(&template) {
+            match (&helper.name, helper.params.first()) {
+                (Parameter::Name(name), Some(Parameter::Literal(Json::String(kind))))
+                    if name == "fake" && !FAKE_KINDS.contains(&kind.as_str()) =>
+                {
+                    return Err(format!(
+                        "invalid template: unknown fake value kind {}, expected one of {}",
+                        kind,
+                        FAKE_KINDS.join(", ")
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        let mut handlebars = Handlebars::new();
+        handlebars.register_escape_fn(handlebars::no_escape);
+        handlebars.register_helper("lower", Box::new(lower));
+        handlebars.register_helper("upper", Box::new(upper));
+        handlebars.register_helper("fake", Box::new(fake_helper));
+        handlebars.register_template("template", template);
+        Ok(TemplateTransformer { handlebars })
+    }
+
+    /// Names of the values referenced by the template expressions, which may
+    /// refer to columns. Helper names and literals are left out.
+    pub fn referenced_names(template: &str) -> Vec<String> {
+        let template = match Template::compile(template) {
+            Ok(template) => template,
+            Err(_) => return vec![],
+        };
+        let mut names = vec![];
+        for helper in helpers(&template) {
+            // `{{name}}` is a value, `{{name param}}` and `{{#name}}` a helper call
+            if helper.params.is_empty() && helper.hash.is_empty() && !helper.block {
+                param_names(&helper.name, &mut names);
+            }
+            for param in helper.params.iter().chain(helper.hash.values()) {
+                param_names(param, &mut names);
+            }
+        }
+        names
+    }
+}
+
+/// Expressions, blocks and subexpressions of the template, in order
+fn helpers(template: &Template) -> Vec<&HelperTemplate> {
+    let mut found = vec![];
+    template_helpers(template, &mut found);
+    found
+}
+
+fn template_helpers<'a>(template: &'a Template, found: &mut Vec<&'a HelperTemplate>) {
+    for element in &template.elements {
+        match element {
+            TemplateElement::Expression(helper)
+            | TemplateElement::HtmlExpression(helper)
+            | TemplateElement::HelperBlock(helper) => nested_helpers(helper, found),
+            _ => {}
+        }
+    }
+}
+
+fn nested_helpers<'a>(helper: &'a HelperTemplate, found: &mut Vec<&'a HelperTemplate>) {
+    found.push(helper);
+    for param in helper.params.iter().chain(helper.hash.values()) {
+        if let Parameter::Subexpression(subexpression) = param {
+            if let TemplateElement::Expression(helper) = subexpression.element.as_ref() {
+                nested_helpers(helper, found);
+            }
+        }
+    }
+    for template in helper.template.iter().chain(&helper.inverse) {
+        template_helpers(template, found);
+    }
+}
+
+fn param_names(param: &Parameter, names: &mut Vec<String>) {
+    match param {
+        Parameter::Name(name) => names.push(name.clone()),
+        Parameter::Path(Path::Relative((_, raw))) => {
+            // the column is the first segment of `column.field` or `this/column`
+            let name = raw
+                .split(['.', '/'])
+                .find(|s| !s.is_empty() && *s != "this");
+            names.extend(name.map(|name| name.to_string()));
+        }
+        _ => {}
+    }
+}
+
+handlebars_helper!(lower: |s: str| s.to_lowercase());
+handlebars_helper!(upper: |s: str| s.to_uppercase());
+
+/// Value kinds supported by the `fake` helper
+const FAKE_KINDS: &[&str] = &[
+    "first-name",
+    "last-name",
+    "email",
+    "street",
+    "city",
+    "state",
+    "zip-code",
+];
+
+/// Renders a fake value of the given kind, e.g. `{{fake "first-name"}}`
+fn fake_helper(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let kind = h
+        .param(0)
+        .and_then(|v| v.value().as_str())
+        .ok_or_else(|| RenderError::new("fake: missing value kind"))?;
+
+    let value: String = match kind {
+        "first-name" => FirstName(EN).fake(),
+        "last-name" => LastName(EN).fake(),
+        "email" => FreeEmail().fake(),
+        "street" => StreetName().fake(),
+        "city" => CityName().fake(),
+        "state" => StateAbbr().fake(),
+        "zip-code" => ZipCode().fake(),
+        _ => {
+            return Err(RenderError::new(format!(
+                "fake: unknown value kind {}",
+                kind
+            )))
+        }
+    };
+
+    out.write(&value)?;
+    Ok(())
+}
+
+impl Transformer for TemplateTransformer {
+    fn id(&self) -> &str {
+        "template"
+    }
+
+    fn description(&self) -> &str {
+        "Handlebars template rendered with the row values"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        let data: HashMap<&str, &str> = ctx
+            .columns
+            .iter()
+            .map(|c| c.as_str())
+            .zip(ctx.transformed.iter().map(|v| v.as_str()))
+            .collect();
+
+        // Fake kinds are checked when the template is built and its columns
+        // with the table's transformers, leaving errors such as unknown helpers
+        self.handlebars
+            .render("template", &data)
+            .unwrap_or_else(|e| {
+                log::warn!(
+                    "Row {} failed to render the template, clearing it: {}",
+                    ctx.index,
+                    e
+                );
+                String::new()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_column;
+
+    #[test]
+    fn test_transform() {
+        let transformer =
+            TemplateTransformer::new("{{lower first}}.{{lower last}}@example.com").unwrap();
+        let value = transform_column(
+            &transformer,
+            &["first", "last", "email"],
+            &["Martin", "O'Moore", "martin@gmail.com"],
+            2,
+        );
+        assert_eq!(value, "martin.o'moore@example.com");
+    }
+
+    #[test]
+    fn test_unknown_fake_kind() {
+        assert!(TemplateTransformer::new(r#"{{fake "city"}}"#).is_ok());
+        assert_eq!(
+            TemplateTransformer::new(r#"{{upper (fake "phone")}}"#).err().unwrap(),
+            "invalid template: unknown fake value kind phone, expected one of first-name, last-name, email, street, city, state, zip-code"
+        );
+    }
+
+    #[test]
+    fn test_referenced_names() {
+        let names = TemplateTransformer::referenced_names(
+            r#"{{fake "first-name"}} {{lower last}} {{#if email}}{{email}}{{else}}{{this.city}}{{/if}} {{address.zip}} {{upper (lower middle)}} {{@index}}"#,
+        );
+        assert_eq!(
+            names,
+            vec!["last", "email", "email", "city", "address", "middle"]
+        );
+        assert!(TemplateTransformer::referenced_names("{{first").is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+use super::{TransformationContext, Transformer};
+use fake::faker::internet::en::DomainSuffix;
+use fake::faker::lorem::en::Word;
+use fake::Fake;
+
+pub struct UrlTransformer {}
+
+impl UrlTransformer {
+    pub fn default() -> Self {
+        UrlTransformer {}
+    }
+}
+
+impl Transformer for UrlTransformer {
+    fn id(&self) -> &str {
+        "url"
+    }
+
+    fn description(&self) -> &str {
+        "Random URL"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        let domain: String = Word().fake();
+        let suffix: String = DomainSuffix().fake();
+        let path: String = Word().fake();
+
+        format!("https://www.{}.{}/{}", domain, suffix, path)
+    }
+}
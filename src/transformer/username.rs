@@ -0,0 +1,29 @@
+use crate::config::Locale;
+
+use super::{localized, TransformationContext, Transformer};
+
+pub struct UsernameTransformer {
+    locale: Locale,
+}
+
+impl UsernameTransformer {
+    pub fn new(locale: &Locale) -> Self {
+        UsernameTransformer {
+            locale: locale.clone(),
+        }
+    }
+}
+
+impl Transformer for UsernameTransformer {
+    fn id(&self) -> &str {
+        "username"
+    }
+
+    fn description(&self) -> &str {
+        "Random username"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        localized!(&self.locale, fake::faker::internet::raw::Username)
+    }
+}
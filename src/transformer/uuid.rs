@@ -0,0 +1,49 @@
+use super::{TransformationContext, Transformer};
+use rand::Rng;
+
+pub struct UuidTransformer {}
+
+impl UuidTransformer {
+    pub fn default() -> Self {
+        UuidTransformer {}
+    }
+}
+
+impl Transformer for UuidTransformer {
+    fn id(&self) -> &str {
+        "uuid"
+    }
+
+    fn description(&self) -> &str {
+        "Random UUID (version 4)"
+    }
+
+    fn transform(&self, _: &TransformationContext) -> String {
+        let mut bytes: [u8; 16] = rand::thread_rng().gen();
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::transform_value;
+
+    #[test]
+    fn test_transform() {
+        let uuid = transform_value(&UuidTransformer::default(), "");
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use sxd_document::writer::Writer;
+use sxd_xpath::nodeset::Node;
+use sxd_xpath::{Context, Factory, Value, XPath};
+
+use super::{TransformationContext, Transformer};
+
+pub struct XmlTransformer {
+    rules: Vec<(XPath, Box<dyn Transformer>)>,
+    namespaces: HashMap<String, String>,
+}
+
+impl XmlTransformer {
+    pub fn new(
+        rules: Vec<(String, Box<dyn Transformer>)>,
+        namespaces: HashMap<String, String>,
+    ) -> Result<Self, String> {
+        let factory = Factory::new();
+        // Names are matched on evaluation, which panics on unknown prefixes,
+        // so they are checked here. String literals and axes are skipped.
+        let names =
+            Regex::new(r#"'[^']*'|"[^"]*"|[A-Za-z_][\w.-]*::|([A-Za-z_][\w.-]*):[A-Za-z_*]"#)
+                .unwrap();
+
+        let rules = rules
+            .into_iter()
+            .map(|(path, transformer)| {
+                let xpath = factory
+                    .build(&path)
+                    .map_err(|e| format!("xml path {}: {}", path, e))?
+                    .ok_or(format!("xml path {} is empty", path))?;
+                for prefix in names.captures_iter(&path).filter_map(|c| c.get(1)) {
+                    if !namespaces.contains_key(prefix.as_str()) {
+                        return Err(format!(
+                            "xml path {}: namespace prefix {} is not in namespaces",
+                            path,
+                            prefix.as_str()
+                        ));
+                    }
+                }
+                Ok((xpath, transformer))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(XmlTransformer { rules, namespaces })
+    }
+}
+
+impl Transformer for XmlTransformer {
+    fn id(&self) -> &str {
+        "xml"
+    }
+
+    fn description(&self) -> &str {
+        "Transforms the elements and attributes of a XML document matching XPath expressions"
+    }
+
+    fn transform(&self, ctx: &TransformationContext) -> String {
+        if ctx.value.is_empty() {
+            return "".to_string();
+        }
+
+        let package = match sxd_document::parser::parse(ctx.value) {
+            Ok(package) => package,
+            Err(e) => {
+                log::warn!("Row {} has invalid XML, clearing it: {:?}", ctx.index, e);
+                return "".to_string();
+            }
+        };
+        let document = package.as_document();
+        let mut context = Context::new();
+        for (prefix, uri) in &self.namespaces {
+            context.set_namespace(prefix, uri);
+        }
+
+        for (xpath, transformer) in &self.rules {
+            let nodes = match xpath.evaluate(&context, document.root()) {
+                Ok(Value::Nodeset(nodes)) => nodes.document_order(),
+                Ok(_) => continue,
+                Err(e) => {
+                    log::warn!(
+                        "Row {} failed to evaluate XPath, clearing it: {}",
+                        ctx.index,
+                        e
+                    );
+                    return "".to_string();
+                }
+            };
+            for node in nodes {
+                let value = node.string_value();
+                let result = transformer.transform(&ctx.with_value(&value));
+                match node {
+                    Node::Element(element) => {
+                        element.set_text(&result);
+                    }
+                    Node::Attribute(attribute) => {
+                        if let Some(element) = attribute.parent() {
+                            element.set_attribute_value(attribute.name(), &result);
+                        }
+                    }
+                    Node::Text(text) => text.set_text(&result),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut output = vec![];
+        Writer::new()
+            .set_single_quotes(false)
+            .format_document(&document, &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        // The writer always adds a declaration, keep it only if there was one
+        if ctx.value.trim_start().starts_with("<?xml") {
+            output
+        } else {
+            output
+                .trim_start_matches("<?xml version=\"1.0\"?>")
+                .to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformer::{transform_value, NullTransformer, ReverseTransformer};
+
+    #[test]
+    fn test_transform() {
+        let transformer = XmlTransformer::new(
+            vec![
+                (
+                    "/patient/name".to_string(),
+                    Box::new(ReverseTransformer::default()),
+                ),
+                (
+                    "/patient/@ssn".to_string(),
+                    Box::new(NullTransformer::default()),
+                ),
+            ],
+            HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            transform_value(
+                &transformer,
+                r#"<patient ssn="123"><name>John</name><age>40</age></patient>"#
+            ),
+            r#"<patient ssn=""><name>nhoJ</name><age>40</age></patient>"#
+        );
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let rules = || -> Vec<(String, Box<dyn Transformer>)> {
+            vec![(
+                "//h:patient/h:name".to_string(),
+                Box::new(ReverseTransformer::default()),
+            )]
+        };
+        assert!(XmlTransformer::new(rules(), HashMap::new()).is_err());
+
+        let namespaces = HashMap::from([("h".to_string(), "urn:hl7-org:v3".to_string())]);
+        let transformer = XmlTransformer::new(rules(), namespaces).unwrap();
+        assert_eq!(
+            transform_value(
+                &transformer,
+                r#"<ClinicalDocument xmlns="urn:hl7-org:v3"><patient><name>John</name></patient></ClinicalDocument>"#
+            ),
+            r#"<ClinicalDocument xmlns='urn:hl7-org:v3'><patient><name>nhoJ</name></patient></ClinicalDocument>"#
+        );
+    }
+}
@@ -1,11 +1,16 @@
-use super::{TransformationContext, Transformer};
-use fake::{faker::address::en::ZipCode, Fake};
+use crate::config::Locale;
 
-pub struct ZipCodeTransformer {}
+use super::{localized, TransformationContext, Transformer};
+
+pub struct ZipCodeTransformer {
+    locale: Locale,
+}
 
 impl ZipCodeTransformer {
-    pub fn default() -> Self {
-        ZipCodeTransformer {}
+    pub fn new(locale: &Locale) -> Self {
+        ZipCodeTransformer {
+            locale: locale.clone(),
+        }
     }
 }
 
@@ -19,6 +24,6 @@ impl Transformer for ZipCodeTransformer {
     }
 
     fn transform(&self, _: &TransformationContext) -> String {
-        ZipCode().fake()
+        localized!(&self.locale, fake::faker::address::raw::ZipCode)
     }
 }
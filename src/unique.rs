@@ -0,0 +1,174 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+
+const RECORD_SIZE: u64 = 16;
+
+/// Sorted hashes spilled to disk, searched in place
+struct Run {
+    file: File,
+    len: u64,
+}
+
+impl Run {
+    fn write(hashes: impl Iterator<Item = u128>) -> Run {
+        let file = tempfile::tempfile().unwrap();
+        let mut writer = BufWriter::new(&file);
+        let mut len = 0;
+        for hash in hashes {
+            writer.write_all(&hash.to_be_bytes()).unwrap();
+            len += 1;
+        }
+        writer.flush().unwrap();
+        drop(writer);
+
+        Run { file, len }
+    }
+
+    fn hashes(&mut self) -> impl Iterator<Item = u128> + '_ {
+        self.file.seek(SeekFrom::Start(0)).unwrap();
+        let mut reader = BufReader::new(&self.file);
+        (0..self.len).map(move |_| {
+            let mut buf = [0u8; RECORD_SIZE as usize];
+            reader.read_exact(&mut buf).unwrap();
+            u128::from_be_bytes(buf)
+        })
+    }
+
+    /// Merges two runs into a new sorted one
+    fn merge(mut self, mut other: Run) -> Run {
+        let mut left = self.hashes().peekable();
+        let mut right = other.hashes().peekable();
+        Run::write(std::iter::from_fn(|| match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) if l <= r => left.next(),
+            (_, Some(_)) => right.next(),
+            _ => left.next(),
+        }))
+    }
+
+    fn contains(&mut self, hash: u128) -> bool {
+        let (mut low, mut high) = (0, self.len);
+        let mut buf = [0u8; RECORD_SIZE as usize];
+
+        while low < high {
+            let mid = (low + high) / 2;
+            self.file.seek(SeekFrom::Start(mid * RECORD_SIZE)).unwrap();
+            self.file.read_exact(&mut buf).unwrap();
+            let value = u128::from_be_bytes(buf);
+            if value == hash {
+                return true;
+            } else if value < hash {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        false
+    }
+}
+
+/// Set of values already issued for a column. Only a 128 bit hash of each
+/// value is kept, and once `max_in_memory` hashes are held they are moved to
+/// a sorted temporary file. Files of similar size are merged, so there are
+/// only a logarithmic number of them to search.
+pub struct UniqueValues {
+    memory: HashSet<u128>,
+    max_in_memory: Option<usize>,
+    runs: Vec<Run>,
+    count: usize,
+}
+
+impl UniqueValues {
+    pub fn new(max_in_memory: Option<usize>) -> Self {
+        UniqueValues {
+            memory: HashSet::new(),
+            max_in_memory,
+            runs: vec![],
+            count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Adds the value to the set, returning false if it was already issued
+    pub fn insert(&mut self, value: &str) -> bool {
+        let hash = hash(value);
+        if self.memory.contains(&hash) || self.runs.iter_mut().any(|r| r.contains(hash)) {
+            return false;
+        }
+
+        self.memory.insert(hash);
+        self.count += 1;
+        if let Some(max) = self.max_in_memory {
+            if self.memory.len() >= max {
+                self.spill();
+            }
+        }
+        true
+    }
+
+    fn spill(&mut self) {
+        let mut hashes: Vec<u128> = self.memory.drain().collect();
+        hashes.sort_unstable();
+        log::debug!("Spilling {} unique values to disk", hashes.len());
+
+        let mut run = Run::write(hashes.into_iter());
+        while let Some(last) = self.runs.pop() {
+            if last.len > run.len {
+                self.runs.push(last);
+                break;
+            }
+            run = last.merge(run);
+        }
+        self.runs.push(run);
+    }
+}
+
+fn hash(value: &str) -> u128 {
+    let half = |seed: u8| {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        value.hash(&mut hasher);
+        hasher.finish() as u128
+    };
+    half(0) << 64 | half(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spilled_values() {
+        let mut values = UniqueValues::new(Some(2));
+        for value in ["a", "b", "c", "d", "e"] {
+            assert!(values.insert(value));
+        }
+        for value in ["a", "b", "c", "d", "e"] {
+            assert!(!values.insert(value));
+        }
+        assert_eq!(values.runs.len(), 1);
+        assert_eq!(values.len(), 5);
+    }
+
+    #[test]
+    fn test_merged_runs() {
+        let mut values = UniqueValues::new(Some(10));
+        for i in 0..1000 {
+            assert!(values.insert(&i.to_string()));
+        }
+        // 100 spills of 10 values are merged as 640 + 320 + 40
+        assert_eq!(
+            values.runs.iter().map(|r| r.len).collect::<Vec<_>>(),
+            vec![640, 320, 40]
+        );
+        for i in 0..1000 {
+            assert!(!values.insert(&i.to_string()));
+        }
+        assert!(values.insert("1000"));
+    }
+}